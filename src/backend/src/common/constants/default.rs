@@ -1,3 +1,6 @@
 pub const DEFAULT_MAX_VALUE_SIZE: u32 = 20_000_000;
 pub const DEFAULT_NANOS_TIME: u64 = 1_000_000_000;
 pub const DEFAULT_EXPIRED_SESSION: u64 = 3 * 60 * 60 * DEFAULT_NANOS_TIME; // 3 Hour
+pub const CURRENT_SCHEMA_VERSION: u32 = 1; // 1: Segment timings in integer milliseconds
+pub const TRANSCRIPTION_PAGE_SEGMENTS: usize = 100; // Segments fetched per /result outcall, keeps replies well under 2 MB
+pub const EVENTS_URL_TTL_SECS: u64 = 10 * 60; // How long a /events URL handed to a browser can be opened
@@ -10,3 +10,5 @@ pub const MEMORY_ID_SUMMARIES: MemoryId = MemoryId::new(6);
 pub const MEMORY_ID_FILE_ARTIFACTS: MemoryId = MemoryId::new(7);
 pub const MEMORY_ID_USER_BOOKMARKS: MemoryId = MemoryId::new(8);
 pub const MEMORY_ID_FILE_CHUNKS: MemoryId = MemoryId::new(9);
+pub const MEMORY_ID_SCHEMA_VERSION: MemoryId = MemoryId::new(10);
+pub const MEMORY_ID_SIGNING_KEY: MemoryId = MemoryId::new(11);
@@ -0,0 +1,15 @@
+use serde::{ de::Error, Deserialize, Deserializer, Serializer };
+
+/// Bytes as a base64 string, the way the transcribe service sends images
+pub fn serialize_base64<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer
+{
+    serializer.serialize_str(&base64::encode(bytes))
+}
+
+pub fn deserialize_base64<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where D: Deserializer<'de>
+{
+    let encoded = String::deserialize(deserializer)?;
+    base64::decode(encoded).map_err(|e| D::Error::custom(format!("Invalid base64: {}", e)))
+}
@@ -1,5 +1,7 @@
+pub mod base64_helper;
 pub mod date_helper;
 pub mod json_helper;
 
+pub use base64_helper::*;
 pub use date_helper::*;
 pub use json_helper::*;
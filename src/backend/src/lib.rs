@@ -2,7 +2,7 @@
 use candid::Principal;
 use getrandom::register_custom_getrandom;
 use ic_cdk::{ init, export_candid, post_upgrade };
-use ic_stable_structures::{ DefaultMemoryImpl, StableBTreeMap };
+use ic_stable_structures::{ DefaultMemoryImpl, StableBTreeMap, StableCell };
 use ic_stable_structures::memory_manager::{ MemoryManager, VirtualMemory };
 use rand::rngs::StdRng;
 use std::cell::RefCell;
@@ -19,6 +19,9 @@ thread_local! {
     // A global random number generator, seeded when the canister is initialized
     static RNG: RefCell<Option<StdRng>> = RefCell::new(None);
 
+    // Requests signed so far, makes the nonce of each outcall unique within a round
+    static SIGNED_REQUESTS: RefCell<u64> = RefCell::new(0);
+
     // Memory Manager
     static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> = RefCell::new(
         MemoryManager::init(DefaultMemoryImpl::default())
@@ -84,18 +87,53 @@ thread_local! {
     static JOBS: RefCell<
         StableBTreeMap<String, String, VirtualMemory<DefaultMemoryImpl>>
     > = RefCell::new(StableBTreeMap::init(MEMORY_MANAGER.with(|m| m.borrow().get(MEMORY_ID_JOBS))));
+
+    // Version of the layout of the values in stable memory, 0 for canisters deployed before it existed
+    static SCHEMA_VERSION: RefCell<
+        StableCell<u32, VirtualMemory<DefaultMemoryImpl>>
+    > = RefCell::new(
+        StableCell::init(MEMORY_MANAGER.with(|m| m.borrow().get(MEMORY_ID_SCHEMA_VERSION)), 0)
+            .expect("Failed to initialize schema version")
+    );
+
+    // Key shared with the transcribe service to sign outgoing requests, empty until a controller sets it
+    static SIGNING_KEY: RefCell<
+        StableCell<String, VirtualMemory<DefaultMemoryImpl>>
+    > = RefCell::new(
+        StableCell::init(MEMORY_MANAGER.with(|m| m.borrow().get(MEMORY_ID_SIGNING_KEY)), String::new())
+            .expect("Failed to initialize signing key")
+    );
 }
 
 #[init]
 fn init() {
+    set_schema_version(CURRENT_SCHEMA_VERSION);
     init_rng();
 }
 
 #[post_upgrade]
 pub fn post_upgrade() {
+    run_migrations();
     init_rng();
 }
 
+/// Bring values written by an older version of the canister up to the current schema
+fn run_migrations() {
+    let version = SCHEMA_VERSION.with(|v| *v.borrow().get());
+
+    if version < 1 {
+        upload::service::migrate_segment_timings();
+    }
+
+    set_schema_version(CURRENT_SCHEMA_VERSION);
+}
+
+fn set_schema_version(version: u32) {
+    SCHEMA_VERSION.with(|v| {
+        v.borrow_mut().set(version).expect("Failed to store schema version");
+    });
+}
+
 register_custom_getrandom!(custom_getrandom);
 
 export_candid!();
@@ -0,0 +1,11 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+/// Formats a transcription can be exported in
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Srt,
+    Vtt,
+    Txt,
+    Json,
+}
@@ -3,7 +3,15 @@ use serde::{ Deserialize, Serialize };
 
 use crate::{
     impl_storable,
-    modules::upload::domain::entities::{ Transcription, Summary, FileArtifactVisibility },
+    modules::upload::domain::entities::{
+        Transcription,
+        Summary,
+        FileArtifactVisibility,
+        Speaker,
+        MediaInfo,
+        Thumbnail,
+        Waveform,
+    },
 };
 
 #[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
@@ -19,6 +27,13 @@ pub struct FileArtifact {
     pub created_at: u64,
     pub deleted_at: Option<u64>,
     pub visibility: FileArtifactVisibility,
+    /// Names given to the speaker labels of the transcription
+    pub speakers: Option<Vec<Speaker>>,
+    /// Duration and streams of the file, missing when it was never probed
+    pub media: Option<MediaInfo>,
+    pub waveform: Option<Waveform>,
+    /// Poster frame, only for videos
+    pub thumbnail: Option<Thumbnail>,
 }
 
 impl_storable!(FileArtifact);
@@ -0,0 +1,13 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use super::JobStage;
+
+/// Step a running transcription job is at, as reported by the transcribe service
+///
+/// Only the coarse stage goes through consensus, the finer progress the service reports keeps
+/// moving while the replicas ask for it. Follow the job's event stream for that.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct JobProgress {
+    pub stage: JobStage,
+}
@@ -0,0 +1,12 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+/// Steps a transcription job goes through in the transcribe service
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum JobStage {
+    Queued,
+    Assembling,
+    Decoding,
+    Transcribing,
+    PostProcessing,
+}
@@ -1,10 +1,14 @@
 use candid::CandidType;
 use serde::Deserialize;
 
+use super::JobProgress;
+
 #[derive(CandidType, Deserialize)]
 #[serde(tag = "status", content = "data")]
 pub enum JobStatus {
     Pending,
+    InProgress(JobProgress),
     Completed(String),
     Failed(String),
+    Cancelled,
 }
@@ -0,0 +1,49 @@
+use candid::{ CandidType, Principal };
+use serde::Deserialize;
+
+use crate::{
+    impl_storable,
+    modules::upload::domain::entities::{ FileArtifact, FileArtifactVisibility, Summary },
+};
+
+use super::LegacyTranscription;
+
+/// File artifact as stored before schema version 1
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct LegacyFileArtifact {
+    pub file_id: String,
+    pub filename: String,
+    pub content_type: String,
+    pub size: u64,
+    pub owner: Principal,
+    pub title: Option<String>,
+    pub transcription: Option<LegacyTranscription>,
+    pub summary: Option<Summary>,
+    pub created_at: u64,
+    pub deleted_at: Option<u64>,
+    pub visibility: FileArtifactVisibility,
+}
+
+impl From<LegacyFileArtifact> for FileArtifact {
+    fn from(artifact: LegacyFileArtifact) -> Self {
+        FileArtifact {
+            file_id: artifact.file_id,
+            filename: artifact.filename,
+            content_type: artifact.content_type,
+            size: artifact.size,
+            owner: artifact.owner,
+            title: artifact.title,
+            transcription: artifact.transcription.map(Into::into),
+            summary: artifact.summary,
+            created_at: artifact.created_at,
+            deleted_at: artifact.deleted_at,
+            visibility: artifact.visibility,
+            speakers: None,
+            media: None,
+            waveform: None,
+            thumbnail: None,
+        }
+    }
+}
+
+impl_storable!(LegacyFileArtifact);
@@ -0,0 +1,36 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::{ impl_storable, modules::upload::domain::entities::Transcription };
+
+use super::LegacyTranscriptionSegment;
+
+/// Transcription as stored before schema version 1
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct LegacyTranscription {
+    pub job_id: String,
+    pub file_id: String,
+    pub text: String,
+    pub language: String,
+    pub segments: Vec<LegacyTranscriptionSegment>,
+    pub created_at: u64,
+    pub deleted_at: Option<u64>,
+}
+
+impl From<LegacyTranscription> for Transcription {
+    fn from(transcription: LegacyTranscription) -> Self {
+        Transcription {
+            job_id: transcription.job_id,
+            file_id: transcription.file_id,
+            text: transcription.text,
+            language: transcription.language,
+            segments: transcription.segments.into_iter().map(Into::into).collect(),
+            model: None,
+            options: None,
+            created_at: transcription.created_at,
+            deleted_at: transcription.deleted_at,
+        }
+    }
+}
+
+impl_storable!(LegacyTranscription);
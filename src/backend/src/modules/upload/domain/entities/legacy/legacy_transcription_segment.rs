@@ -0,0 +1,32 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::modules::upload::domain::entities::TranscriptionSegment;
+
+/// Segment as stored before schema version 1, times in float seconds
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct LegacyTranscriptionSegment {
+    pub id: u32,
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+}
+
+impl From<LegacyTranscriptionSegment> for TranscriptionSegment {
+    fn from(segment: LegacyTranscriptionSegment) -> Self {
+        TranscriptionSegment {
+            id: segment.id,
+            start_ms: seconds_to_ms(segment.start),
+            end_ms: seconds_to_ms(segment.end),
+            text: segment.text,
+            words: None,
+            no_speech_prob: None,
+            avg_logprob: None,
+            speaker: None,
+        }
+    }
+}
+
+fn seconds_to_ms(seconds: f32) -> u64 {
+    ((seconds.max(0.0) as f64) * 1000.0).round() as u64
+}
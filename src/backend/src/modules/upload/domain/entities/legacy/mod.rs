@@ -0,0 +1,7 @@
+pub mod legacy_file_artifact;
+pub mod legacy_transcription;
+pub mod legacy_transcription_segment;
+
+pub use legacy_file_artifact::*;
+pub use legacy_transcription::*;
+pub use legacy_transcription_segment::*;
@@ -0,0 +1,13 @@
+use candid::CandidType;
+use serde::{ Deserialize, Serialize };
+
+use super::MediaStream;
+
+/// Container, duration and streams of an uploaded file, probed by the transcribe service
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct MediaInfo {
+    pub container: Option<String>,
+    pub duration_ms: Option<u64>,
+    pub size: u64,
+    pub streams: Vec<MediaStream>,
+}
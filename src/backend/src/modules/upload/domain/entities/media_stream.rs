@@ -0,0 +1,17 @@
+use candid::CandidType;
+use serde::{ Deserialize, Serialize };
+
+use super::StreamKind;
+
+/// A track of an uploaded file as reported by the transcribe service
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct MediaStream {
+    pub index: u32,
+    pub kind: StreamKind,
+    pub codec: Option<String>,
+    pub duration_ms: Option<u64>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
@@ -5,20 +5,33 @@ pub mod file_artifact_request;
 pub mod file_artifact_visibility;
 pub mod user_file_artifact;
 pub mod user_bookmark;
+pub mod export_format;
 pub mod file_artifact;
 pub mod file_chunk;
 pub mod file_type_filter;
+pub mod job_progress;
+pub mod job_stage;
 pub mod job_status;
 pub mod language_filter;
+pub mod legacy;
 pub mod llm_response;
+pub mod media_info;
+pub mod media_stream;
 pub mod sort_order_filter;
+pub mod speaker;
 pub mod start_upload_request;
+pub mod stream_kind;
 pub mod summary;
+pub mod thumbnail;
 pub mod transcription_segment;
 pub mod transcription;
+pub mod transcription_options;
+pub mod transcription_result;
+pub mod transcription_word;
 pub mod upload_chunk_request;
 pub mod upload_file;
 pub mod upload_session;
+pub mod waveform;
 
 pub use download_chunk_request::*;
 pub use download_chunk_response::*;
@@ -27,17 +40,30 @@ pub use file_artifact_request::*;
 pub use file_artifact_visibility::*;
 pub use user_file_artifact::*;
 pub use user_bookmark::*;
+pub use export_format::*;
 pub use file_artifact::*;
 pub use file_chunk::*;
 pub use file_type_filter::*;
+pub use job_progress::*;
+pub use job_stage::*;
 pub use job_status::*;
 pub use language_filter::*;
+pub use legacy::*;
 pub use llm_response::*;
+pub use media_info::*;
+pub use media_stream::*;
 pub use sort_order_filter::*;
+pub use speaker::*;
 pub use start_upload_request::*;
+pub use stream_kind::*;
 pub use summary::*;
+pub use thumbnail::*;
 pub use transcription_segment::*;
 pub use transcription::*;
+pub use transcription_options::*;
+pub use transcription_result::*;
+pub use transcription_word::*;
 pub use upload_chunk_request::*;
 pub use upload_file::*;
 pub use upload_session::*;
+pub use waveform::*;
@@ -0,0 +1,9 @@
+use candid::CandidType;
+use serde::{ Deserialize, Serialize };
+
+/// Display name given by the owner to a speaker label found in the transcription
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct Speaker {
+    pub label: String,
+    pub name: String,
+}
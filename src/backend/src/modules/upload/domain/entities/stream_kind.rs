@@ -0,0 +1,9 @@
+use candid::CandidType;
+use serde::{ Deserialize, Serialize };
+
+#[derive(CandidType, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StreamKind {
+    Audio,
+    Video,
+    Other,
+}
@@ -0,0 +1,12 @@
+use candid::CandidType;
+use serde::{ Deserialize, Serialize };
+
+use crate::common::{ deserialize_base64, serialize_base64 };
+
+/// Poster frame of a video file
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct Thumbnail {
+    pub content_type: String,
+    #[serde(serialize_with = "serialize_base64", deserialize_with = "deserialize_base64")]
+    pub data: Vec<u8>,
+}
@@ -2,7 +2,7 @@ use candid::CandidType;
 use serde::{ Deserialize, Serialize };
 
 use crate::{ impl_storable };
-use super::TranscriptionSegment;
+use super::{ TranscriptionOptions, TranscriptionSegment };
 
 #[derive(CandidType, Clone, Serialize, Deserialize, Debug)]
 pub struct Transcription {
@@ -11,6 +11,9 @@ pub struct Transcription {
     pub text: String,
     pub language: String,
     pub segments: Vec<TranscriptionSegment>,
+    /// Model and settings that produced the transcript, missing on older transcripts
+    pub model: Option<String>,
+    pub options: Option<TranscriptionOptions>,
     pub created_at: u64,
     pub deleted_at: Option<u64>,
 }
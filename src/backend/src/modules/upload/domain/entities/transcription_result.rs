@@ -0,0 +1,50 @@
+use serde::Deserialize;
+
+use super::{ Thumbnail, TranscriptionOptions, TranscriptionSegment, Waveform };
+
+/// Transcript as the transcribe service returns it from `/result`
+///
+/// A page of the transcript when fetched with `offset` and `limit`, `segments` then only
+/// holds part of the `total_segments`.
+#[derive(Deserialize, Debug)]
+pub struct TranscriptionResult {
+    pub text: String,
+    pub language: String,
+    pub segments: Vec<TranscriptionSegment>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub options: Option<TranscriptionOptions>,
+    #[serde(default)]
+    pub waveform: Option<Waveform>,
+    #[serde(default)]
+    pub thumbnail: Option<Thumbnail>,
+    #[serde(default)]
+    pub total_segments: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TranscriptionResult;
+
+    /// First page of the mock engine's transcript, as the transcribe service returns it
+    const MOCK_RESULT: &str = include_str!(
+        "../../../../../../transcribe/tests/fixtures/mock_result.json"
+    );
+
+    #[test]
+    fn parses_mock_result() {
+        let result: TranscriptionResult = serde_json::from_str(MOCK_RESULT).unwrap();
+
+        assert_eq!(result.language, "en");
+        assert_eq!(result.model.as_deref(), Some("base"));
+        assert_eq!(result.total_segments, Some(3));
+        assert_eq!(result.segments.len(), 3);
+        assert_eq!(result.segments[0].text, "Hello and welcome to Transkripin.");
+        assert_eq!(result.segments[0].words.as_ref().map(|w| w.len()), Some(5));
+
+        let waveform = result.waveform.expect("waveform on the first page");
+        assert_eq!(waveform.duration_ms, 8000);
+        assert!(result.thumbnail.is_none());
+    }
+}
@@ -3,12 +3,21 @@ use serde::{ Deserialize, Serialize };
 
 use crate::impl_storable;
 
+use super::TranscriptionWord;
+
+/// A transcribed span of the media, times are integer milliseconds from the start
 #[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionSegment {
     pub id: u32,
-    pub start: f32,
-    pub end: f32,
+    pub start_ms: u64,
+    pub end_ms: u64,
     pub text: String,
+    /// Word timings, missing on transcripts stored before they were reported
+    pub words: Option<Vec<TranscriptionWord>>,
+    pub no_speech_prob: Option<f32>,
+    pub avg_logprob: Option<f32>,
+    /// Raw speaker label from diarization, see `FileArtifact::speakers` for display names
+    pub speaker: Option<String>,
 }
 
 impl_storable!(TranscriptionSegment);
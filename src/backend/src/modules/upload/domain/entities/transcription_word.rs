@@ -0,0 +1,11 @@
+use candid::CandidType;
+use serde::{ Deserialize, Serialize };
+
+/// A single word of a segment, with the lowest probability among its tokens
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionWord {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub probability: f32,
+}
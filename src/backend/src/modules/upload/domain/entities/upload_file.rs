@@ -1,7 +1,10 @@
 use candid::{ CandidType, Principal };
 use serde::{ Deserialize, Serialize };
 
-use crate::impl_storable;
+use crate::{
+    impl_storable,
+    modules::upload::domain::entities::{ MediaInfo, Thumbnail, Waveform },
+};
 
 #[derive(CandidType, Serialize, Deserialize, Clone)]
 pub struct UploadedFile {
@@ -13,6 +16,11 @@ pub struct UploadedFile {
     pub owner: Principal,
     pub created_at: u64,
     pub deleted_at: Option<u64>,
+    /// Probed by the transcribe service when the file is first sent for transcription
+    pub media: Option<MediaInfo>,
+    /// Set once the file has been transcribed
+    pub waveform: Option<Waveform>,
+    pub thumbnail: Option<Thumbnail>,
 }
 
 impl_storable!(UploadedFile);
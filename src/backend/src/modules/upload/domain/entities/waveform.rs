@@ -0,0 +1,10 @@
+use candid::CandidType;
+use serde::{ Deserialize, Serialize };
+
+/// Loudness outline of a file, one peak per equal slice of the audio
+#[derive(CandidType, Debug, Clone, Serialize, Deserialize)]
+pub struct Waveform {
+    /// 255 is the loudest slice of the file
+    pub peaks: Vec<u8>,
+    pub duration_ms: u64,
+}
@@ -13,12 +13,14 @@ use crate::{
                 FileArtifactVisibility,
                 JobStatus,
                 LlmResponse,
+                Speaker,
                 Summary,
                 UserBookmarks,
                 UserFileArtifact,
             },
             service::{
                 call_ollama,
+                cancel_transcription_job,
                 fetch_file_artifacts,
                 save_file_artifact,
                 check_artifact_accessible,
@@ -209,7 +211,7 @@ pub fn edit_file_artifact(request: FileArtifactRequest) -> Result<FileArtifact,
 
 /// Delete a file artifact
 #[update]
-pub fn delete_file_artifact(file_id: String) -> Result<(), String> {
+pub async fn delete_file_artifact(file_id: String) -> Result<(), String> {
     let caller = ic_cdk::api::caller();
 
     // Fetch and check ownership
@@ -226,6 +228,22 @@ pub fn delete_file_artifact(file_id: String) -> Result<(), String> {
         map.borrow_mut().remove(&file_id);
     });
 
+    // Stop the remote jobs of the file, they would only produce a result nobody can see
+    let job_ids: Vec<String> = JOBS.with(|jobs| {
+        jobs.borrow()
+            .iter()
+            .filter(|(_, job_file_id)| *job_file_id == file_id)
+            .map(|(job_id, _)| job_id)
+            .collect()
+    });
+
+    for job_id in job_ids {
+        if let Err(e) = cancel_transcription_job(&job_id).await {
+            ic_cdk::println!("{}", e);
+        }
+        JOBS.with(|jobs| jobs.borrow_mut().remove(&job_id));
+    }
+
     // Remove related Transcription
     TRANSCRIPTIONS.with(|map| {
         map.borrow_mut().remove(&file_id);
@@ -335,6 +353,48 @@ pub fn toggle_file_artifact_visibility(file_id: String) -> Result<String, String
     })
 }
 
+/// Give a display name to a speaker label, an empty name resets it to the label
+#[update]
+pub fn rename_speaker(file_id: String, label: String, name: String) -> Result<String, String> {
+    let caller = ic_cdk::api::caller();
+    let name = name.trim().to_string();
+
+    FILE_ARTIFACTS.with(|map| {
+        let mut store = map.borrow_mut();
+
+        let Some(mut artifact) = store.get(&file_id) else {
+            return Err(format!("File artifact with id {} not found", file_id));
+        };
+
+        if artifact.owner != caller {
+            return Err("Unauthorized: You are not the owner".to_string());
+        }
+
+        let known_label = artifact.transcription
+            .as_ref()
+            .map(|t| t.segments.iter().any(|s| s.speaker.as_deref() == Some(label.as_str())))
+            .unwrap_or(false);
+        if !known_label {
+            return Err(format!("Speaker {} not found in the transcription", label));
+        }
+
+        let mut speakers = artifact.speakers.take().unwrap_or_default();
+        speakers.retain(|s| s.label != label);
+        if !name.is_empty() {
+            speakers.push(Speaker { label: label.clone(), name: name.clone() });
+        }
+        artifact.speakers = Some(speakers);
+
+        store.insert(file_id.clone(), artifact);
+
+        if name.is_empty() {
+            Ok(format!("Speaker {} name reset", label))
+        } else {
+            Ok(format!("Speaker {} renamed to {}", label, name))
+        }
+    })
+}
+
 /// Bookmark a file artifact
 #[update]
 pub fn toggle_file_artifact_bookmark(file_id: String) -> Result<String, String> {
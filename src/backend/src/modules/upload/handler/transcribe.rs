@@ -1,27 +1,65 @@
-use ic_cdk::{ query, update };
+use ic_cdk::{
+    api::management_canister::http_request::{ HttpResponse, TransformArgs },
+    query,
+    update,
+};
 
 use crate::{
     modules::upload::{
-        domain::entities::{ JobStatus, Transcription },
-        service::{ call_transcription, fetch_transcription_api },
+        domain::entities::{
+            ExportFormat,
+            JobStatus,
+            Transcription,
+            TranscriptionOptions,
+        },
+        service::{
+            call_transcription,
+            cancel_transcription_job,
+            check_artifact_accessible,
+            check_job_owner,
+            fetch_transcription_api,
+            fetch_transcription_result,
+            normalize_finalize_response,
+            normalize_transcription_response,
+            render_transcription,
+            save_media_preview,
+            transcription_events_url,
+        },
     },
+    FILE_ARTIFACTS,
     JOBS,
+    SIGNING_KEY,
     TRANSCRIPTIONS,
     UPLOADED_FILES,
 };
 
 /* Transcription */
 #[update]
-pub async fn start_transcription(file_id: String) -> Result<String, String> {
+pub async fn start_transcription(
+    file_id: String,
+    options: Option<TranscriptionOptions>
+) -> Result<String, String> {
     UPLOADED_FILES.with(|files| files.borrow().get(&file_id).ok_or("File not found".to_string()))?;
 
-    let job_id = call_transcription(file_id.clone()).await?;
+    let job_id = call_transcription(file_id.clone(), options).await?;
 
     JOBS.with(|jobs| jobs.borrow_mut().insert(job_id.clone(), file_id));
 
     Ok(job_id)
 }
 
+/// Stop a running transcription and forget about it
+#[update]
+pub async fn cancel_transcription(job_id: String) -> Result<String, String> {
+    check_job_owner(&job_id, ic_cdk::api::caller())?;
+
+    let message = cancel_transcription_job(&job_id).await?;
+
+    JOBS.with(|jobs| jobs.borrow_mut().remove(&job_id));
+
+    Ok(message)
+}
+
 #[query]
 pub fn get_transcription(file_id: String) -> Result<String, String> {
     TRANSCRIPTIONS.with(|map| {
@@ -32,59 +70,99 @@ pub fn get_transcription(file_id: String) -> Result<String, String> {
     })
 }
 
+/// Render the transcription of a file as subtitles or a document
+#[query]
+pub fn export_transcription(file_id: String, format: ExportFormat) -> Result<String, String> {
+    let caller = ic_cdk::api::caller();
+
+    let artifact = FILE_ARTIFACTS.with(|map| map.borrow().get(&file_id))
+        .filter(|artifact| check_artifact_accessible(&caller, artifact))
+        .ok_or(format!("File artifact with id {} not found", file_id))?;
+
+    let transcription = artifact.transcription
+        .as_ref()
+        .ok_or("No transcription found".to_string())?;
+
+    render_transcription(transcription, artifact.speakers.as_deref().unwrap_or_default(), format)
+}
+
+/// Signed URL for the owner of a job to follow it live from the browser
+///
+/// The URL stops working after a few minutes, ask for a new one to reconnect.
+#[query]
+pub fn get_transcription_events_url(job_id: String) -> Result<String, String> {
+    check_job_owner(&job_id, ic_cdk::api::caller())?;
+
+    Ok(transcription_events_url(&job_id))
+}
+
+/// Status of a job of the caller's
 #[update]
 pub async fn get_transcription_status(job_id: String) -> Result<JobStatus, String> {
-    fetch_transcription_api(&job_id, "status", |status_str| {
-        serde_json::from_str(&status_str).map_err(|e| format!("Invalid JSON: {:?}", e))
+    check_job_owner(&job_id, ic_cdk::api::caller())?;
+
+    fetch_transcription_api(&job_id, "status", "", |status_str| {
+        let mut status: serde_json::Value = serde_json
+            ::from_str(&status_str)
+            .map_err(|e| format!("Invalid JSON: {:?}", e))?;
+
+        // The service leaves the transcript out of the status, it is fetched with
+        // `get_transcription_result`
+        if status["status"] == "Completed" {
+            status["data"] = serde_json::Value::String(String::new());
+        }
+
+        serde_json::from_value(status).map_err(|e| format!("Invalid JSON: {:?}", e))
     }).await
 }
 
+/// Store the transcript of a completed job of the caller's and return its text
 #[update]
 pub async fn get_transcription_result(job_id: String) -> Result<String, String> {
-    fetch_transcription_api(&job_id, "result", |result_str| {
-        // Insert into TRANSCRIPTIONS map if job_id exists
-        if let Some(file_id) = JOBS.with(|jobs| jobs.borrow().get(&job_id)) {
-            TRANSCRIPTIONS.with(|map| {
-                let file_id_clone = file_id.clone();
-                let created_at = ic_cdk::api::time();
-
-                let (text, language, segments) = match
-                    serde_json::from_str::<serde_json::Value>(&result_str)
-                {
-                    Ok(parsed) => {
-                        let text = parsed
-                            .get("text")
-                            .and_then(|v| v.as_str().map(|s| s.to_string()))
-                            .unwrap_or_else(|| result_str.clone());
-
-                        let language = parsed
-                            .get("language")
-                            .and_then(|v| v.as_str().map(|s| s.to_string()))
-                            .unwrap_or_else(|| "unknown".to_string());
-
-                        let segments = parsed
-                            .get("segments")
-                            .and_then(|v| serde_json::from_value(v.clone()).ok())
-                            .unwrap_or_else(|| vec![]);
-                        (text, language, segments)
-                    }
-                    Err(_) => (result_str.clone(), "unknown".to_string(), vec![]),
-                };
-
-                map.borrow_mut().insert(file_id_clone, Transcription {
-                    job_id: job_id.clone(),
-                    file_id: file_id.clone(),
-                    text: text,
-                    language: language,
-                    segments: segments,
-                    created_at: created_at,
-                    deleted_at: None,
-                });
-            });
-        } else {
-            return Err("No file ID found for this job ID".to_string());
-        }
+    let file_id = check_job_owner(&job_id, ic_cdk::api::caller())?;
 
-        Ok(result_str)
-    }).await
+    let result = fetch_transcription_result(&job_id).await?;
+    let text = result.text.clone();
+
+    TRANSCRIPTIONS.with(|map| {
+        map.borrow_mut().insert(file_id.clone(), Transcription {
+            job_id: job_id.clone(),
+            file_id: file_id.clone(),
+            text: result.text,
+            language: result.language,
+            segments: result.segments,
+            model: result.model,
+            options: result.options,
+            created_at: ic_cdk::api::time(),
+            deleted_at: None,
+        });
+    });
+
+    save_media_preview(&file_id, result.waveform, result.thumbnail);
+
+    Ok(text)
+}
+
+/// Set the key shared with the transcribe service, an empty key stops signing requests
+#[update]
+pub fn set_transcription_signing_key(key: String) -> Result<(), String> {
+    if !ic_cdk::api::is_controller(&ic_cdk::api::caller()) {
+        return Err("Only controllers can set the signing key".to_string());
+    }
+
+    SIGNING_KEY.with(|k| k.borrow_mut().set(key))
+        .map(|_| ())
+        .map_err(|e| format!("Failed to store signing key: {:?}", e))
+}
+
+/// Run by each replica on the replies of the transcribe service, before they agree on one
+#[query(hidden = true)]
+pub fn transform_transcription_response(args: TransformArgs) -> HttpResponse {
+    normalize_transcription_response(args.response)
+}
+
+/// Run by each replica on the reply to a finalize, before they agree on one
+#[query(hidden = true)]
+pub fn transform_finalize_response(args: TransformArgs) -> HttpResponse {
+    normalize_finalize_response(args.response)
 }
@@ -112,6 +112,9 @@ pub async fn complete_upload(session_id: String) -> Result<String, String> {
                     created_at: session.created_at,
                     owner: caller,
                     deleted_at: None,
+                    media: None,
+                    waveform: None,
+                    thumbnail: None,
                 })
             }
             None => Err("Upload session not found".to_string()),
@@ -7,12 +7,23 @@ use ic_cdk::api::management_canister::http_request::{
 };
 use crate::{
     common::constants::uri::TRANSCRIPTION_URL,
-    modules::upload::domain::entities::FileChunk,
+    modules::upload::domain::entities::{ FileChunk, TranscriptionOptions },
     FILE_CHUNKS,
     UPLOADED_FILES,
 };
 
-pub async fn call_transcription(file_id: String) -> Result<String, String> {
+use super::{
+    declare_transcription_session,
+    finalize_transform,
+    probe_uploaded_file,
+    read_service_response,
+    sign_request,
+};
+
+pub async fn call_transcription(
+    file_id: String,
+    options: Option<TranscriptionOptions>
+) -> Result<String, String> {
     // Load metadata
     let file = UPLOADED_FILES.with(|files| {
         files.borrow().get(&file_id).ok_or("File not found".to_string())
@@ -21,7 +32,10 @@ pub async fn call_transcription(file_id: String) -> Result<String, String> {
     let boundary = "----ic_boundary";
     let session_id = file.id.clone();
 
-    for chunk_index in 0..file.total_chunks {
+    // Only what the service does not have yet, everything on the first try
+    let missing_chunks = declare_transcription_session(&file).await?;
+
+    for chunk_index in missing_chunks {
         let key = FileChunk {
             id: file.id.clone(),
             chunk_index: chunk_index,
@@ -71,7 +85,7 @@ pub async fn call_transcription(file_id: String) -> Result<String, String> {
         );
 
         // Construct request
-        let request = CanisterHttpRequestArgument {
+        let mut request = CanisterHttpRequestArgument {
             url: format!("{}/upload_chunk", TRANSCRIPTION_URL),
             method: HttpMethod::POST,
             headers: vec![HttpHeader {
@@ -83,20 +97,28 @@ pub async fn call_transcription(file_id: String) -> Result<String, String> {
             transform: None,
         };
 
-        http_request(request, cycles.into()).await.map_err(|e|
+        sign_request(&mut request);
+
+        let (response,): (HttpResponse,) = http_request(request, cycles.into()).await.map_err(|e|
             format!("Chunk {} upload failed: {:?}", chunk_index, e)
         )?;
+        read_service_response(response, "upload_chunk")?;
+    }
+
+    // Best effort, a file the service cannot probe may still be transcribed with ffmpeg
+    if let Err(e) = probe_uploaded_file(&file.id).await {
+        ic_cdk::println!("Probe of file {} failed: {}", file.id, e);
     }
 
     // Tell server we're done uploading
     let finalize_body = serde_json
-        ::to_vec(&serde_json::json!({ "session_id": session_id }))
+        ::to_vec(&serde_json::json!({ "session_id": session_id, "options": options }))
         .unwrap();
     let request_size = finalize_body.len() as u64;
     let response_size = 2_000_000u64;
     let cycles = 400_000_000 + (request_size + response_size) * 600_000;
 
-    let finalize_request = CanisterHttpRequestArgument {
+    let mut finalize_request = CanisterHttpRequestArgument {
         url: format!("{}/finalize_upload", TRANSCRIPTION_URL),
         method: HttpMethod::POST,
         headers: vec![HttpHeader {
@@ -105,23 +127,22 @@ pub async fn call_transcription(file_id: String) -> Result<String, String> {
         }],
         body: Some(finalize_body),
         max_response_bytes: Some(response_size),
-        transform: None,
+        transform: Some(finalize_transform()),
     };
 
+    sign_request(&mut finalize_request);
+
     let (response,): (HttpResponse,) = http_request(finalize_request, cycles.into()).await.map_err(
         |e| format!("Finalize request failed: {:?}", e)
     )?;
 
-    let finalize_result = String::from_utf8(response.body).map_err(|_|
-        "Invalid UTF-8 in finalize response".to_string()
-    )?;
+    let finalize_result = read_service_response(response, "finalize_upload")?;
 
     let job_id: String = serde_json
         ::from_str::<serde_json::Value>(&finalize_result)
         .map_err(|_| "Invalid JSON in finalize response".to_string())?
-        .get("message")
-        .and_then(|m| m.as_str())
-        .and_then(|m| m.strip_prefix("Job started with ID: "))
+        .get("job_id")
+        .and_then(|id| id.as_str())
         .ok_or("Missing job_id in finalize response".to_string())?
         .to_string();
 
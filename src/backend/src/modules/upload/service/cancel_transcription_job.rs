@@ -0,0 +1,48 @@
+use candid::Nat;
+use ic_cdk::api::management_canister::http_request::{
+    http_request,
+    CanisterHttpRequestArgument,
+    HttpMethod,
+    HttpResponse,
+};
+
+use crate::common::TRANSCRIPTION_URL;
+
+use super::{ read_service_response, sign_request };
+
+/// Ask the transcribe service to stop a job, or to drop its result if it already finished
+///
+/// HTTP outcalls cannot send DELETE, so this goes through the POST alias of `DELETE /jobs/{id}`.
+/// A job the service no longer knows about counts as cancelled.
+pub async fn cancel_transcription_job(job_id: &str) -> Result<String, String> {
+    let response_size = 10_000u64;
+    let cycles = 400_000_000 + response_size * 600_000;
+
+    let mut req = CanisterHttpRequestArgument {
+        url: format!("{}/jobs/{}/cancel", TRANSCRIPTION_URL, job_id),
+        method: HttpMethod::POST,
+        headers: vec![],
+        body: None,
+        max_response_bytes: Some(response_size),
+        transform: None,
+    };
+
+    sign_request(&mut req);
+
+    let (res,): (HttpResponse,) = http_request(req, cycles.into()).await.map_err(|e|
+        format!("cancel request failed: {:?}", e)
+    )?;
+
+    if res.status == Nat::from(404u32) {
+        return Ok(format!("Job {} is already gone", job_id));
+    }
+    let body = read_service_response(res, "cancel")?;
+
+    Ok(
+        serde_json
+            ::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(|m| m.to_string()))
+            .unwrap_or(body)
+    )
+}
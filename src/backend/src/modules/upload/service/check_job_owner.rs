@@ -0,0 +1,19 @@
+use candid::Principal;
+
+use crate::{ JOBS, UPLOADED_FILES };
+
+/// File a transcription job belongs to, as long as the caller owns that file
+///
+/// A job whose file is gone has no owner left, so nobody may use it.
+pub fn check_job_owner(job_id: &str, caller: Principal) -> Result<String, String> {
+    let file_id = JOBS.with(|jobs| jobs.borrow().get(&job_id.to_string())).ok_or(
+        "Job not found".to_string()
+    )?;
+
+    let owner = UPLOADED_FILES.with(|files| files.borrow().get(&file_id).map(|f| f.owner));
+    if owner != Some(caller) {
+        return Err("Unauthorized: You are not the owner".to_string());
+    }
+
+    Ok(file_id)
+}
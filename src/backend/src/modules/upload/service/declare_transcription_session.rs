@@ -0,0 +1,80 @@
+use ic_cdk::api::management_canister::http_request::{
+    http_request,
+    CanisterHttpRequestArgument,
+    HttpHeader,
+    HttpMethod,
+    HttpResponse,
+};
+use sha2::{ Digest, Sha256 };
+
+use crate::{
+    common::constants::uri::TRANSCRIPTION_URL,
+    modules::upload::domain::entities::{ FileChunk, UploadedFile },
+    FILE_CHUNKS,
+};
+
+use super::{ read_service_response, sign_request };
+
+/// Declare an upload to the transcribe service and get back the chunks it still needs
+///
+/// The declaration carries the size and SHA-256 of the whole file, so the service refuses
+/// to transcribe an upload with holes or corrupted chunks. Declaring a session that already
+/// exists is harmless, which lets a failed transcription resume with just the missing chunks.
+pub async fn declare_transcription_session(file: &UploadedFile) -> Result<Vec<u64>, String> {
+    let mut total_size = 0u64;
+    let mut hasher = Sha256::new();
+
+    for chunk_index in 0..file.total_chunks {
+        let key = FileChunk {
+            id: file.id.clone(),
+            chunk_index: chunk_index,
+        };
+
+        let chunk = FILE_CHUNKS.with(|chunks| {
+            chunks.borrow().get(&key).ok_or(format!("Chunk {} not found", chunk_index))
+        })?;
+
+        total_size += chunk.len() as u64;
+        hasher.update(&chunk);
+    }
+
+    let body = serde_json
+        ::to_vec(
+            &serde_json::json!({
+                "session_id": file.id,
+                "total_chunks": file.total_chunks,
+                "total_size": total_size,
+                "sha256": hex::encode(hasher.finalize()),
+            })
+        )
+        .unwrap();
+    let request_size = body.len() as u64;
+    let response_size = 100_000u64;
+    let cycles = 400_000_000 + (request_size + response_size) * 600_000;
+
+    let mut request = CanisterHttpRequestArgument {
+        url: format!("{}/sessions", TRANSCRIPTION_URL),
+        method: HttpMethod::POST,
+        headers: vec![HttpHeader {
+            name: "Content-Type".to_string(),
+            value: "application/json".to_string(),
+        }],
+        body: Some(body),
+        max_response_bytes: Some(response_size),
+        transform: None,
+    };
+
+    sign_request(&mut request);
+
+    let (response,): (HttpResponse,) = http_request(request, cycles.into()).await.map_err(|e|
+        format!("Session request failed: {:?}", e)
+    )?;
+    let session = read_service_response(response, "sessions")?;
+
+    serde_json
+        ::from_str::<serde_json::Value>(&session)
+        .map_err(|_| "Invalid JSON in sessions response".to_string())?
+        .get("missing_chunks")
+        .and_then(|m| serde_json::from_value(m.clone()).ok())
+        .ok_or("Missing missing_chunks in sessions response".to_string())
+}
@@ -7,9 +7,13 @@ use ic_cdk::api::management_canister::http_request::{
 
 use crate::common::TRANSCRIPTION_URL;
 
+use super::{ read_service_response, sign_request, transcription_transform };
+
+/// GET `/<endpoint>/<job_id>?<query>` from the transcribe service and parse the reply
 pub async fn fetch_transcription_api<T, F>(
     job_id: &str,
     endpoint: &str,
+    query: &str,
     parse_fn: F
 ) -> Result<T, String>
     where F: FnOnce(String) -> Result<T, String>
@@ -17,22 +21,27 @@ pub async fn fetch_transcription_api<T, F>(
     let response_size = 2_000_000u64;
     let cycles = 400_000_000 + response_size * 600_000;
 
-    let req = CanisterHttpRequestArgument {
-        url: format!("{}/{}/{}", TRANSCRIPTION_URL, endpoint, job_id),
+    let mut url = format!("{}/{}/{}", TRANSCRIPTION_URL, endpoint, job_id);
+    if !query.is_empty() {
+        url = format!("{}?{}", url, query);
+    }
+
+    let mut req = CanisterHttpRequestArgument {
+        url,
         method: HttpMethod::GET,
         headers: vec![],
         body: None,
         max_response_bytes: Some(response_size),
-        transform: None,
+        transform: Some(transcription_transform()),
     };
 
+    sign_request(&mut req);
+
     let (res,): (HttpResponse,) = http_request(req, cycles.into()).await.map_err(|e|
         format!("{} request failed: {:?}", endpoint, e)
     )?;
 
-    let body_str = String::from_utf8(res.body).map_err(|_|
-        format!("Invalid UTF-8 in {} response", endpoint)
-    )?;
+    let body_str = read_service_response(res, endpoint)?;
 
     parse_fn(body_str)
 }
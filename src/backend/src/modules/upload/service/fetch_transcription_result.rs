@@ -0,0 +1,44 @@
+use crate::{
+    common::TRANSCRIPTION_PAGE_SEGMENTS,
+    modules::upload::domain::entities::TranscriptionResult,
+};
+
+use super::fetch_transcription_api;
+
+/// Transcript of a completed job, fetched a page of segments at a time
+///
+/// The result of a long recording does not fit in a single outcall reply, so `/result` is
+/// read in ranges of `TRANSCRIPTION_PAGE_SEGMENTS` until every segment is in.
+pub async fn fetch_transcription_result(job_id: &str) -> Result<TranscriptionResult, String> {
+    let mut result = fetch_result_page(job_id, 0).await?;
+    let total_segments = result.total_segments.unwrap_or(result.segments.len());
+
+    while result.segments.len() < total_segments {
+        let page = fetch_result_page(job_id, result.segments.len()).await?;
+        if page.segments.is_empty() {
+            return Err(
+                format!(
+                    "Transcription for job {} ended after {} of {} segments",
+                    job_id,
+                    result.segments.len(),
+                    total_segments
+                )
+            );
+        }
+        result.segments.extend(page.segments);
+    }
+
+    Ok(result)
+}
+
+async fn fetch_result_page(job_id: &str, offset: usize) -> Result<TranscriptionResult, String> {
+    let query = format!("offset={}&limit={}", offset, TRANSCRIPTION_PAGE_SEGMENTS);
+
+    // Failed jobs come back as an error status, and a running one as its progress,
+    // neither parses as a transcript so nothing but a real result is stored
+    fetch_transcription_api(job_id, "result", &query, |result_str| {
+        serde_json
+            ::from_str::<TranscriptionResult>(&result_str)
+            .map_err(|_| format!("Transcription for job {} is not ready yet", job_id))
+    }).await
+}
@@ -0,0 +1,158 @@
+use ic_stable_structures::StableBTreeMap;
+
+use crate::{
+    common::constants::{ MEMORY_ID_FILE_ARTIFACTS, MEMORY_ID_TRANSCRIPTIONS },
+    modules::upload::domain::entities::{
+        FileArtifact,
+        LegacyFileArtifact,
+        LegacyTranscription,
+        Transcription,
+    },
+    FILE_ARTIFACTS,
+    MEMORY_MANAGER,
+    TRANSCRIPTIONS,
+};
+
+/// Schema version 1: rewrite stored segments from float seconds to integer milliseconds
+///
+/// The old entries cannot be decoded as the new types (and an `opt` transcription inside a
+/// file artifact would silently decode as `null`), so each map is read back with its legacy
+/// type and then rebuilt from scratch in the same memory.
+pub fn migrate_segment_timings() {
+    let transcriptions: Vec<(String, LegacyTranscription)> = {
+        let memory = MEMORY_MANAGER.with(|m| m.borrow().get(MEMORY_ID_TRANSCRIPTIONS));
+        let legacy: StableBTreeMap<String, LegacyTranscription, _> = StableBTreeMap::init(memory);
+        legacy.iter().collect()
+    };
+
+    let file_artifacts: Vec<(String, LegacyFileArtifact)> = {
+        let memory = MEMORY_MANAGER.with(|m| m.borrow().get(MEMORY_ID_FILE_ARTIFACTS));
+        let legacy: StableBTreeMap<String, LegacyFileArtifact, _> = StableBTreeMap::init(memory);
+        legacy.iter().collect()
+    };
+
+    let mut migrated_transcriptions: StableBTreeMap<String, Transcription, _> = StableBTreeMap::new(
+        MEMORY_MANAGER.with(|m| m.borrow().get(MEMORY_ID_TRANSCRIPTIONS))
+    );
+    for (file_id, transcription) in transcriptions {
+        migrated_transcriptions.insert(file_id, transcription.into());
+    }
+
+    let mut migrated_file_artifacts: StableBTreeMap<String, FileArtifact, _> = StableBTreeMap::new(
+        MEMORY_MANAGER.with(|m| m.borrow().get(MEMORY_ID_FILE_ARTIFACTS))
+    );
+    for (file_id, artifact) in file_artifacts {
+        migrated_file_artifacts.insert(file_id, artifact.into());
+    }
+
+    TRANSCRIPTIONS.with(|map| {
+        *map.borrow_mut() = migrated_transcriptions;
+    });
+    FILE_ARTIFACTS.with(|map| {
+        *map.borrow_mut() = migrated_file_artifacts;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use candid::Principal;
+    use ic_stable_structures::StableBTreeMap;
+
+    use super::migrate_segment_timings;
+    use crate::{
+        common::constants::{ CURRENT_SCHEMA_VERSION, MEMORY_ID_FILE_ARTIFACTS },
+        modules::upload::domain::entities::{
+            FileArtifact,
+            FileArtifactVisibility,
+            LegacyFileArtifact,
+            LegacyTranscription,
+            LegacyTranscriptionSegment,
+        },
+        run_migrations,
+        set_schema_version,
+        FILE_ARTIFACTS,
+        MEMORY_MANAGER,
+        SCHEMA_VERSION,
+    };
+
+    const FILE_ID: &str = "file-1";
+
+    /// Artifact as schema version 0 stored it, segment times in float seconds
+    fn legacy_artifact() -> LegacyFileArtifact {
+        let segment = |id: u32, start: f32, end: f32, text: &str| LegacyTranscriptionSegment {
+            id,
+            start,
+            end,
+            text: text.to_string(),
+        };
+
+        LegacyFileArtifact {
+            file_id: FILE_ID.to_string(),
+            filename: "talk.mp3".to_string(),
+            content_type: "audio/mpeg".to_string(),
+            size: 1024,
+            owner: Principal::anonymous(),
+            title: Some("Talk".to_string()),
+            transcription: Some(LegacyTranscription {
+                job_id: "job-1".to_string(),
+                file_id: FILE_ID.to_string(),
+                text: "Hello there. General Kenobi.".to_string(),
+                language: "en".to_string(),
+                segments: vec![
+                    segment(0, 0.0, 1.25, "Hello there."),
+                    segment(1, 1.25, 2.345, "General Kenobi.")
+                ],
+                created_at: 1,
+                deleted_at: None,
+            }),
+            summary: None,
+            created_at: 1,
+            deleted_at: None,
+            visibility: FileArtifactVisibility::Private,
+        }
+    }
+
+    fn stored_artifact() -> FileArtifact {
+        FILE_ARTIFACTS.with(|map| map.borrow().get(&FILE_ID.to_string())).expect("Stored artifact")
+    }
+
+    fn segment_timings(artifact: &FileArtifact) -> Vec<(u64, u64)> {
+        artifact.transcription
+            .as_ref()
+            .expect("Transcription")
+            .segments.iter()
+            .map(|segment| (segment.start_ms, segment.end_ms))
+            .collect()
+    }
+
+    #[test]
+    fn converts_legacy_artifact() {
+        {
+            let memory = MEMORY_MANAGER.with(|m| m.borrow().get(MEMORY_ID_FILE_ARTIFACTS));
+            let mut legacy: StableBTreeMap<String, LegacyFileArtifact, _> =
+                StableBTreeMap::init(memory);
+            legacy.insert(FILE_ID.to_string(), legacy_artifact());
+        }
+
+        migrate_segment_timings();
+
+        let artifact = stored_artifact();
+        assert_eq!(segment_timings(&artifact), vec![(0, 1250), (1250, 2345)]);
+        assert_eq!(artifact.title.as_deref(), Some("Talk"));
+        assert_eq!(artifact.transcription.unwrap().segments[1].text, "General Kenobi.");
+        assert!(artifact.speakers.is_none());
+    }
+
+    #[test]
+    fn current_schema_is_left_alone() {
+        set_schema_version(CURRENT_SCHEMA_VERSION);
+        let artifact: FileArtifact = legacy_artifact().into();
+        FILE_ARTIFACTS.with(|map| map.borrow_mut().insert(FILE_ID.to_string(), artifact));
+
+        // Running the migration again would read the artifact back as a legacy one and fail
+        run_migrations();
+
+        assert_eq!(segment_timings(&stored_artifact()), vec![(0, 1250), (1250, 2345)]);
+        assert_eq!(SCHEMA_VERSION.with(|v| *v.borrow().get()), CURRENT_SCHEMA_VERSION);
+    }
+}
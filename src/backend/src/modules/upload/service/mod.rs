@@ -1,17 +1,43 @@
 pub mod call_ollama;
 pub mod call_transcription;
+pub mod cancel_transcription_job;
 pub mod check_artifact_accessible;
 pub mod check_artifact_visibility;
+pub mod check_job_owner;
+pub mod declare_transcription_session;
 pub mod fetch_file_artifacts;
 pub mod fetch_transcription;
+pub mod fetch_transcription_result;
 pub mod filter_file_artifacts;
+pub mod migrate_segment_timings;
+pub mod probe_uploaded_file;
+pub mod read_service_response;
+pub mod render_transcription;
 pub mod save_file_artifact;
+pub mod save_media_preview;
+pub mod sign_request;
+pub mod transcription_events_url;
+pub mod transform_finalize_response;
+pub mod transform_transcription_response;
 
 pub use call_ollama::*;
 pub use call_transcription::*;
+pub use cancel_transcription_job::*;
 pub use check_artifact_accessible::*;
 pub use check_artifact_visibility::*;
+pub use check_job_owner::*;
+pub use declare_transcription_session::*;
 pub use fetch_file_artifacts::*;
 pub use fetch_transcription::*;
+pub use fetch_transcription_result::*;
 pub use filter_file_artifacts::*;
+pub use migrate_segment_timings::*;
+pub use probe_uploaded_file::*;
+pub use read_service_response::*;
+pub use render_transcription::*;
 pub use save_file_artifact::*;
+pub use save_media_preview::*;
+pub use sign_request::*;
+pub use transcription_events_url::*;
+pub use transform_finalize_response::*;
+pub use transform_transcription_response::*;
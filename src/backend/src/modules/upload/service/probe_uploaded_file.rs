@@ -0,0 +1,67 @@
+use ic_cdk::api::management_canister::http_request::{
+    http_request,
+    CanisterHttpRequestArgument,
+    HttpHeader,
+    HttpMethod,
+    HttpResponse,
+};
+
+use crate::{
+    common::constants::uri::TRANSCRIPTION_URL,
+    modules::upload::domain::entities::MediaInfo,
+    FILE_ARTIFACTS,
+    UPLOADED_FILES,
+};
+
+use super::{ read_service_response, sign_request };
+
+/// Ask the transcribe service for the duration and streams of a fully sent file, and keep them
+///
+/// The metadata is stored on the uploaded file, and on its artifact if it already has one.
+pub async fn probe_uploaded_file(file_id: &str) -> Result<MediaInfo, String> {
+    let body = serde_json::to_vec(&serde_json::json!({ "session_id": file_id })).unwrap();
+    let request_size = body.len() as u64;
+    let response_size = 100_000u64;
+    let cycles = 400_000_000 + (request_size + response_size) * 600_000;
+
+    let mut request = CanisterHttpRequestArgument {
+        url: format!("{}/probe", TRANSCRIPTION_URL),
+        method: HttpMethod::POST,
+        headers: vec![HttpHeader {
+            name: "Content-Type".to_string(),
+            value: "application/json".to_string(),
+        }],
+        body: Some(body),
+        max_response_bytes: Some(response_size),
+        transform: None,
+    };
+
+    sign_request(&mut request);
+
+    let (response,): (HttpResponse,) = http_request(request, cycles.into()).await.map_err(|e|
+        format!("Probe request failed: {:?}", e)
+    )?;
+    let probe = read_service_response(response, "probe")?;
+
+    let media: MediaInfo = serde_json
+        ::from_str(&probe)
+        .map_err(|e| format!("Invalid JSON in probe response: {:?}", e))?;
+
+    let file_id = file_id.to_string();
+    UPLOADED_FILES.with(|files| {
+        let mut files = files.borrow_mut();
+        if let Some(mut file) = files.get(&file_id) {
+            file.media = Some(media.clone());
+            files.insert(file_id.clone(), file);
+        }
+    });
+    FILE_ARTIFACTS.with(|map| {
+        let mut map = map.borrow_mut();
+        if let Some(mut artifact) = map.get(&file_id) {
+            artifact.media = Some(media.clone());
+            map.insert(file_id.clone(), artifact);
+        }
+    });
+
+    Ok(media)
+}
@@ -0,0 +1,27 @@
+use candid::Nat;
+use ic_cdk::api::management_canister::http_request::HttpResponse;
+
+/// Body of a transcribe service reply, or its error message when the request was rejected
+///
+/// Errors come back as `{ "error": "<code>", "message": "..." }` with a 4xx or 5xx status.
+pub fn read_service_response(response: HttpResponse, endpoint: &str) -> Result<String, String> {
+    let body = String::from_utf8(response.body).map_err(|_|
+        format!("Invalid UTF-8 in {} response", endpoint)
+    )?;
+
+    if response.status < Nat::from(400u32) {
+        return Ok(body);
+    }
+
+    let message = serde_json
+        ::from_str::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|v| {
+            let code = v.get("error")?.as_str()?.to_string();
+            let message = v.get("message")?.as_str()?.to_string();
+            Some(format!("{} ({})", message, code))
+        })
+        .unwrap_or(body);
+
+    Err(format!("{} request failed with status {}: {}", endpoint, response.status, message))
+}
@@ -0,0 +1,59 @@
+use captions::{ render_srt, render_txt, render_vtt, CaptionSegment, CaptionWord };
+
+use crate::modules::upload::domain::entities::{
+    ExportFormat,
+    Speaker,
+    Transcription,
+    TranscriptionSegment,
+};
+
+/// Render a transcription as a downloadable document, speakers shown by the names given to them
+///
+/// Subtitles and text come from the `captions` crate, which the transcribe service renders with
+/// too.
+pub fn render_transcription(
+    transcription: &Transcription,
+    speakers: &[Speaker],
+    format: ExportFormat
+) -> Result<String, String> {
+    let mut transcription = transcription.clone();
+    for segment in &mut transcription.segments {
+        if let Some(label) = &segment.speaker {
+            if let Some(speaker) = speakers.iter().find(|s| s.label == *label) {
+                segment.speaker = Some(speaker.name.clone());
+            }
+        }
+    }
+    let segments = || caption_segments(&transcription.segments);
+
+    Ok(match format {
+        ExportFormat::Json =>
+            serde_json
+                ::to_string_pretty(&transcription)
+                .map_err(|e| format!("Failed to serialize transcription: {}", e))?,
+        ExportFormat::Srt => render_srt(&segments()),
+        ExportFormat::Vtt => render_vtt(&segments()),
+        ExportFormat::Txt => render_txt(&segments()),
+    })
+}
+
+fn caption_segments(segments: &[TranscriptionSegment]) -> Vec<CaptionSegment> {
+    segments
+        .iter()
+        .map(|segment| CaptionSegment {
+            start_ms: segment.start_ms,
+            end_ms: segment.end_ms,
+            text: segment.text.clone(),
+            speaker: segment.speaker.clone(),
+            words: segment.words
+                .iter()
+                .flatten()
+                .map(|w| CaptionWord {
+                    text: w.text.clone(),
+                    start_ms: w.start_ms,
+                    end_ms: w.end_ms,
+                })
+                .collect(),
+        })
+        .collect()
+}
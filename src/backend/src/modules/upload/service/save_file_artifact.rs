@@ -6,11 +6,21 @@ use crate::{
     UPLOADED_FILES,
 };
 
+/// Create or refresh the artifact of a file
+///
+/// Saving again, as summarizing a second time does, keeps what the owner set on the
+/// existing artifact: its visibility and speaker names.
 pub fn save_file_artifact(request: FileArtifactRequest) {
     let file_id = &request.file_id.to_string();
     let uploaded_file = UPLOADED_FILES.with(|files| files.borrow().get(file_id));
+    let existing = FILE_ARTIFACTS.with(|map| map.borrow().get(file_id));
 
     if let Some(f) = uploaded_file {
+        let (visibility, speakers) = match existing {
+            Some(existing) => (existing.visibility, existing.speakers),
+            None => (FileArtifactVisibility::Private, None),
+        };
+
         let file_artifact = FileArtifact {
             file_id: f.id.clone(),
             owner: f.owner.clone(),
@@ -22,7 +32,11 @@ pub fn save_file_artifact(request: FileArtifactRequest) {
             title: request.title,
             transcription: request.transcription,
             summary: request.summary,
-            visibility: FileArtifactVisibility::Private,
+            visibility,
+            speakers,
+            media: f.media.clone(),
+            waveform: f.waveform.clone(),
+            thumbnail: f.thumbnail.clone(),
         };
 
         FILE_ARTIFACTS.with(|map| {
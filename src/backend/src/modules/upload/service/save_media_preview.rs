@@ -0,0 +1,31 @@
+use crate::{
+    modules::upload::domain::entities::{ Thumbnail, Waveform },
+    FILE_ARTIFACTS,
+    UPLOADED_FILES,
+};
+
+/// Keep the waveform and thumbnail of a transcribed file, on its artifact too if it has one
+pub fn save_media_preview(
+    file_id: &str,
+    waveform: Option<Waveform>,
+    thumbnail: Option<Thumbnail>
+) {
+    let file_id = file_id.to_string();
+
+    UPLOADED_FILES.with(|files| {
+        let mut files = files.borrow_mut();
+        if let Some(mut file) = files.get(&file_id) {
+            file.waveform = waveform.clone();
+            file.thumbnail = thumbnail.clone();
+            files.insert(file_id.clone(), file);
+        }
+    });
+    FILE_ARTIFACTS.with(|map| {
+        let mut map = map.borrow_mut();
+        if let Some(mut artifact) = map.get(&file_id) {
+            artifact.waveform = waveform;
+            artifact.thumbnail = thumbnail;
+            map.insert(file_id.clone(), artifact);
+        }
+    });
+}
@@ -0,0 +1,119 @@
+use hmac::{ Hmac, Mac };
+use ic_cdk::api::management_canister::http_request::{
+    CanisterHttpRequestArgument,
+    HttpHeader,
+    HttpMethod,
+};
+use sha2::{ Digest, Sha256 };
+
+use crate::{ common::DEFAULT_NANOS_TIME, SIGNED_REQUESTS, SIGNING_KEY };
+
+/// Sign an outcall to the transcribe service with the shared key
+///
+/// The signature is the hex HMAC-SHA256 of `signed_message`, which covers the SHA-256 of the
+/// body sent along in its own header, so the service can check it before reading the body.
+/// Every replica sees the same consensus time and counts the same requests, so they all send
+/// the same request with the same nonce, which the service only runs once.
+/// Requests go out unsigned while no key is configured.
+pub fn sign_request(request: &mut CanisterHttpRequestArgument) {
+    let key = SIGNING_KEY.with(|k| k.borrow().get().clone());
+    if key.is_empty() {
+        return;
+    }
+
+    let now = ic_cdk::api::time();
+    let timestamp = now / DEFAULT_NANOS_TIME;
+    let nonce = SIGNED_REQUESTS.with(|count| {
+        let mut count = count.borrow_mut();
+        *count += 1;
+        format!("{}-{}", now, count)
+    });
+    let method = match request.method {
+        HttpMethod::GET => "GET",
+        HttpMethod::POST => "POST",
+        HttpMethod::HEAD => "HEAD",
+    };
+
+    let body_sha256 = hex::encode(Sha256::digest(request.body.as_deref().unwrap_or_default()));
+    let message = signed_message(timestamp, &nonce, method, url_path(&request.url), &body_sha256);
+    let signature = sign_message(&key, message.as_bytes());
+
+    request.headers.push(HttpHeader {
+        name: "X-Transcribe-Timestamp".to_string(),
+        value: timestamp.to_string(),
+    });
+    request.headers.push(HttpHeader {
+        name: "X-Transcribe-Nonce".to_string(),
+        value: nonce,
+    });
+    request.headers.push(HttpHeader {
+        name: "X-Transcribe-Content-SHA256".to_string(),
+        value: body_sha256,
+    });
+    request.headers.push(HttpHeader {
+        name: "X-Transcribe-Signature".to_string(),
+        value: signature,
+    });
+}
+
+/// What a request signature covers:
+/// `<timestamp>\n<nonce>\n<METHOD>\n<path?query>\n<hex SHA-256 of body>`
+pub fn signed_message(
+    timestamp: u64,
+    nonce: &str,
+    method: &str,
+    path: &str,
+    body_sha256: &str
+) -> String {
+    format!("{}\n{}\n{}\n{}\n{}", timestamp, nonce, method, path, body_sha256)
+}
+
+/// Hex encoded HMAC-SHA256 of a message, as the transcribe service checks it
+pub fn sign_message(key: &str, message: &[u8]) -> String {
+    // HMAC takes keys of any length, this cannot fail
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts any key");
+    mac.update(message);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Path and query of a URL, `/` when it has none
+fn url_path(url: &str) -> &str {
+    let without_scheme = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    match without_scheme.find('/') {
+        Some(i) => &without_scheme[i..],
+        None => "/",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::{ Digest, Sha256 };
+
+    use super::{ sign_message, signed_message, url_path };
+
+    /// Signed request the transcribe service's tests check with their verifier too
+    const SIGNED_REQUEST: &str = include_str!(
+        "../../../../../transcribe/tests/fixtures/signed_request.json"
+    );
+
+    #[test]
+    fn signs_like_the_service_verifies() {
+        let vector: serde_json::Value = serde_json::from_str(SIGNED_REQUEST).unwrap();
+        let field = |name: &str| vector[name].as_str().unwrap().to_string();
+
+        let path = url_path(&field("url")).to_string();
+        assert_eq!(path, field("path"));
+
+        let body_sha256 = hex::encode(Sha256::digest(field("body").as_bytes()));
+        assert_eq!(body_sha256, field("content_sha256"));
+
+        let message = signed_message(
+            vector["timestamp"].as_u64().unwrap(),
+            &field("nonce"),
+            &field("method"),
+            &path,
+            &body_sha256
+        );
+        assert_eq!(sign_message(&field("key"), message.as_bytes()), field("signature"));
+    }
+}
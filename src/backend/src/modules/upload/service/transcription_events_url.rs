@@ -0,0 +1,24 @@
+use crate::{ common::{ DEFAULT_NANOS_TIME, EVENTS_URL_TTL_SECS, TRANSCRIPTION_URL }, SIGNING_KEY };
+
+use super::sign_message;
+
+/// URL a browser can open with `EventSource` to follow a job of the transcribe service
+///
+/// The service wants a token for the stream, `<expires>.<hex HMAC-SHA256>` of
+/// `events\n<job_id>\n<expires>`. It is only valid for this job and expires after
+/// `EVENTS_URL_TTL_SECS`, so a new URL has to be asked for to reconnect later.
+/// Without a signing key the service takes the stream unsigned.
+pub fn transcription_events_url(job_id: &str) -> String {
+    let url = format!("{}/events/{}", TRANSCRIPTION_URL, job_id);
+
+    let key = SIGNING_KEY.with(|k| k.borrow().get().clone());
+    if key.is_empty() {
+        return url;
+    }
+
+    let expires = ic_cdk::api::time() / DEFAULT_NANOS_TIME + EVENTS_URL_TTL_SECS;
+    let message = format!("events\n{}\n{}", job_id, expires);
+    let signature = sign_message(&key, message.as_bytes());
+
+    format!("{}?token={}.{}", url, expires, signature)
+}
@@ -0,0 +1,41 @@
+use candid::Nat;
+use ic_cdk::api::management_canister::http_request::{ HttpResponse, TransformContext };
+
+/// Query every replica runs on the transcribe service's reply to a finalize
+const TRANSFORM_METHOD: &str = "transform_finalize_response";
+
+/// What the service puts before the job id in a finalize reply
+const JOB_STARTED_PREFIX: &str = "Job started with ID: ";
+
+/// Transform to set on the finalize outcall
+pub fn finalize_transform() -> TransformContext {
+    TransformContext::from_name(TRANSFORM_METHOD.to_string(), vec![])
+}
+
+/// Reduce a finalize reply to `{ "job_id": "..." }`, the only part of it the canister uses
+///
+/// Headers are dropped, and so is the message around the job id, so replicas agree on the
+/// job whatever the service says about it. Rejections keep their status and body, they are
+/// the same for every replica.
+pub fn normalize_finalize_response(response: HttpResponse) -> HttpResponse {
+    let job_id = serde_json
+        ::from_slice::<serde_json::Value>(&response.body)
+        .ok()
+        .and_then(|json| {
+            json.get("message")?.as_str()?.strip_prefix(JOB_STARTED_PREFIX).map(str::to_string)
+        });
+
+    let body = match job_id {
+        Some(job_id) if response.status < Nat::from(400u32) => {
+            serde_json::to_vec(&serde_json::json!({ "job_id": job_id })).unwrap_or_default()
+        }
+        _ => response.body,
+    };
+
+    HttpResponse {
+        status: response.status,
+        headers: vec![],
+        body,
+    }
+}
+
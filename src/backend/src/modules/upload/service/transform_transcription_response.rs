@@ -0,0 +1,34 @@
+use ic_cdk::api::management_canister::http_request::{ HttpResponse, TransformContext };
+
+/// Query every replica runs on a transcribe service reply before comparing it
+const TRANSFORM_METHOD: &str = "transform_transcription_response";
+
+/// Transform to set on outcalls whose reply may differ from one replica to the next
+pub fn transcription_transform() -> TransformContext {
+    TransformContext::from_name(TRANSFORM_METHOD.to_string(), vec![])
+}
+
+/// Make the replies replicas got for the same outcall identical, so they reach consensus
+///
+/// Headers such as `Date` change between replicas and are dropped. A running job keeps
+/// moving while the replicas ask for it, so of its progress only the stage is kept: its
+/// percentage, queue position and ETA can each differ from one replica to the next.
+pub fn normalize_transcription_response(response: HttpResponse) -> HttpResponse {
+    let body = match serde_json::from_slice::<serde_json::Value>(&response.body) {
+        Ok(mut json) => {
+            if json["status"] == "InProgress" {
+                let stage = json["data"].get("stage").cloned().unwrap_or_default();
+                json["data"] = serde_json::json!({ "stage": stage });
+            }
+            serde_json::to_vec(&json).unwrap_or(response.body)
+        }
+        // Not JSON, such as an exported transcript, nothing in it moves
+        Err(_) => response.body,
+    };
+
+    HttpResponse {
+        status: response.status,
+        headers: vec![],
+        body,
+    }
+}
@@ -0,0 +1,13 @@
+use super::CaptionWord;
+
+/// A transcribed span of the media, as much of it as rendering needs
+#[derive(Debug, Clone, Default)]
+pub struct CaptionSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+    /// Name shown for the speaker, when the transcript was diarized
+    pub speaker: Option<String>,
+    /// Word timings, the time of the segment is shared out by word length without them
+    pub words: Vec<CaptionWord>,
+}
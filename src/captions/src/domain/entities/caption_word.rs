@@ -0,0 +1,7 @@
+/// A single word of a segment, used to place the cuts between cues
+#[derive(Debug, Clone)]
+pub struct CaptionWord {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
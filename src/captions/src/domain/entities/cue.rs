@@ -0,0 +1,7 @@
+/// A caption shown on screen between two timestamps
+pub(crate) struct Cue {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub speaker: Option<String>,
+    pub lines: Vec<String>,
+}
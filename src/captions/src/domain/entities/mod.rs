@@ -0,0 +1,7 @@
+pub mod caption_segment;
+pub mod caption_word;
+pub mod cue;
+
+pub use caption_segment::*;
+pub use caption_word::*;
+pub(crate) use cue::*;
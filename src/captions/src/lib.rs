@@ -0,0 +1,8 @@
+//! Subtitle and plain text rendering of transcripts, shared by the transcribe service and the
+//! canister so both export the same files
+
+pub mod domain;
+pub mod service;
+
+pub use domain::*;
+pub use service::*;
@@ -0,0 +1,11 @@
+/// `HH:MM:SS,mmm` for SRT, `HH:MM:SS.mmm` for WebVTT
+pub(crate) fn format_timestamp(ms: u64, separator: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        (ms / 60_000) % 60,
+        (ms / 1000) % 60,
+        separator,
+        ms % 1000
+    )
+}
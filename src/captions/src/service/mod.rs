@@ -0,0 +1,13 @@
+pub mod format_timestamp;
+pub mod render_srt;
+pub mod render_txt;
+pub mod render_vtt;
+pub mod split_cues;
+pub mod wrap_text;
+
+pub(crate) use format_timestamp::*;
+pub use render_srt::*;
+pub use render_txt::*;
+pub use render_vtt::*;
+pub(crate) use split_cues::*;
+pub(crate) use wrap_text::*;
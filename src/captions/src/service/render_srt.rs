@@ -0,0 +1,28 @@
+use crate::domain::CaptionSegment;
+
+use super::{ format_timestamp, split_cues };
+
+/// Numbered SubRip cues, the speaker named on the first line of a segment
+pub fn render_srt(segments: &[CaptionSegment]) -> String {
+    let mut out = String::new();
+
+    for (i, cue) in split_cues(segments).iter().enumerate() {
+        out.push_str(&format!("{}\n", i + 1));
+        out.push_str(
+            &format!(
+                "{} --> {}\n",
+                format_timestamp(cue.start_ms, ','),
+                format_timestamp(cue.end_ms, ',')
+            )
+        );
+        for (j, line) in cue.lines.iter().enumerate() {
+            match (&cue.speaker, j) {
+                (Some(speaker), 0) => out.push_str(&format!("{}: {}\n", speaker, line)),
+                _ => out.push_str(&format!("{}\n", line)),
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
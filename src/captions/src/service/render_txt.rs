@@ -0,0 +1,32 @@
+use crate::domain::CaptionSegment;
+
+use super::wrap_text;
+
+const TEXT_LINE_CHARS: usize = 80;
+
+/// One paragraph per segment, the speaker named whenever it changes
+pub fn render_txt(segments: &[CaptionSegment]) -> String {
+    let mut out = String::new();
+    let mut last_speaker: Option<&str> = None;
+
+    for segment in segments {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let speaker = segment.speaker.as_deref();
+        let text = match speaker {
+            Some(speaker) if last_speaker != Some(speaker) => format!("{}: {}", speaker, text),
+            _ => text.to_string(),
+        };
+        last_speaker = speaker;
+
+        for line in wrap_text(&text, TEXT_LINE_CHARS) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+
+    out
+}
@@ -0,0 +1,26 @@
+use crate::domain::CaptionSegment;
+
+use super::{ format_timestamp, split_cues };
+
+/// WebVTT cues, the speaker given as a voice span
+pub fn render_vtt(segments: &[CaptionSegment]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+
+    for cue in split_cues(segments) {
+        out.push_str(
+            &format!(
+                "{} --> {}\n",
+                format_timestamp(cue.start_ms, '.'),
+                format_timestamp(cue.end_ms, '.')
+            )
+        );
+        let text = cue.lines.join("\n");
+        match &cue.speaker {
+            Some(speaker) => out.push_str(&format!("<v {}>{}\n", speaker, text)),
+            None => out.push_str(&format!("{}\n", text)),
+        }
+        out.push('\n');
+    }
+
+    out
+}
@@ -0,0 +1,92 @@
+use crate::domain::{ CaptionSegment, Cue };
+
+use super::wrap_text;
+
+/// Common subtitle limits, two lines of 42 characters per cue
+const CAPTION_LINE_CHARS: usize = 42;
+const CAPTION_LINES: usize = 2;
+
+/// Split segments into cues that fit on screen
+///
+/// Word timings are used to place the cuts when whisper reported them, otherwise the time of
+/// the segment is shared out by the length of each piece.
+pub(crate) fn split_cues(segments: &[CaptionSegment]) -> Vec<Cue> {
+    let mut cues = Vec::new();
+
+    for segment in segments {
+        let words: Vec<(String, u64, u64)> = if segment.words.is_empty() {
+            interpolate_words(segment)
+        } else {
+            segment.words
+                .iter()
+                .map(|w| (w.text.clone(), w.start_ms, w.end_ms))
+                .collect()
+        };
+        if words.is_empty() {
+            continue;
+        }
+
+        let mut first = true;
+        let mut current: Vec<&(String, u64, u64)> = Vec::new();
+        let mut text = String::new();
+
+        for word in &words {
+            let candidate = if text.is_empty() {
+                word.0.clone()
+            } else {
+                format!("{} {}", text, word.0)
+            };
+            if !current.is_empty() && wrap_text(&candidate, CAPTION_LINE_CHARS).len() > CAPTION_LINES {
+                cues.push(cue_from_words(&current, segment, first));
+                first = false;
+                current.clear();
+                text = word.0.clone();
+            } else {
+                text = candidate;
+            }
+            current.push(word);
+        }
+        if !current.is_empty() {
+            cues.push(cue_from_words(&current, segment, first));
+        }
+    }
+
+    cues
+}
+
+fn cue_from_words(words: &[&(String, u64, u64)], segment: &CaptionSegment, first: bool) -> Cue {
+    let text = words
+        .iter()
+        .map(|w| w.0.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Cue {
+        start_ms: words[0].1.max(segment.start_ms),
+        end_ms: words[words.len() - 1].2.min(segment.end_ms).max(words[0].1),
+        speaker: if first { segment.speaker.clone() } else { None },
+        lines: wrap_text(&text, CAPTION_LINE_CHARS),
+    }
+}
+
+/// Give each word of a segment a share of its time proportional to its length
+fn interpolate_words(segment: &CaptionSegment) -> Vec<(String, u64, u64)> {
+    let words: Vec<&str> = segment.text.split_whitespace().collect();
+    let total_chars = words
+        .iter()
+        .map(|w| w.chars().count() as u64)
+        .sum::<u64>()
+        .max(1);
+    let duration = segment.end_ms.saturating_sub(segment.start_ms);
+
+    let mut elapsed = 0;
+    words
+        .into_iter()
+        .map(|word| {
+            let start = segment.start_ms + (duration * elapsed) / total_chars;
+            elapsed += word.chars().count() as u64;
+            let end = segment.start_ms + (duration * elapsed) / total_chars;
+            (word.to_string(), start, end)
+        })
+        .collect()
+}
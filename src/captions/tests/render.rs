@@ -0,0 +1,78 @@
+use captions::{ render_srt, render_vtt, CaptionSegment, CaptionWord };
+
+/// A segment whose time is shared out by word length
+fn segment(start_ms: u64, end_ms: u64, text: &str) -> CaptionSegment {
+    CaptionSegment {
+        start_ms,
+        end_ms,
+        text: text.to_string(),
+        ..Default::default()
+    }
+}
+
+/// A segment of `count` words `wordNN`, 500ms apart, spoken by `speaker`
+fn timed_segment(count: u64, speaker: &str) -> CaptionSegment {
+    let words: Vec<CaptionWord> = (1..=count)
+        .map(|i| CaptionWord {
+            text: format!("word{:02}", i),
+            start_ms: (i - 1) * 500,
+            end_ms: (i - 1) * 500 + 400,
+        })
+        .collect();
+
+    CaptionSegment {
+        start_ms: 0,
+        end_ms: count * 500,
+        text: words
+            .iter()
+            .map(|w| w.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        speaker: Some(speaker.to_string()),
+        words,
+    }
+}
+
+#[test]
+fn renders_subtitles() {
+    let long_word = "x".repeat(50);
+
+    let cases: Vec<(&str, Vec<CaptionSegment>, String, String)> = vec![
+        (
+            "hours roll over",
+            vec![segment(3_599_999, 3_600_001, "Hi"), segment(36_000_000, 36_001_000, "Late")],
+            "1\n00:59:59,999 --> 01:00:00,001\nHi\n\n2\n10:00:00,000 --> 10:00:01,000\nLate\n\n".into(),
+            "WEBVTT\n\n00:59:59.999 --> 01:00:00.001\nHi\n\n10:00:00.000 --> 10:00:01.000\nLate\n\n".into(),
+        ),
+        (
+            "a word longer than a line keeps a line of its own",
+            vec![segment(0, 5_400, &format!("a {} b", long_word))],
+            format!(
+                "1\n00:00:00,000 --> 00:00:05,296\na\n{}\n\n2\n00:00:05,296 --> 00:00:05,400\nb\n\n",
+                long_word
+            ),
+            format!(
+                "WEBVTT\n\n00:00:00.000 --> 00:00:05.296\na\n{}\n\n00:00:05.296 --> 00:00:05.400\nb\n\n",
+                long_word
+            ),
+        ),
+        (
+            "a segment longer than two lines is cut at a word",
+            vec![timed_segment(14, "Ana")],
+            "1\n00:00:00,000 --> 00:00:05,900\n\
+             Ana: word01 word02 word03 word04 word05 word06\n\
+             word07 word08 word09 word10 word11 word12\n\n\
+             2\n00:00:06,000 --> 00:00:06,900\nword13 word14\n\n".into(),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:05.900\n\
+             <v Ana>word01 word02 word03 word04 word05 word06\n\
+             word07 word08 word09 word10 word11 word12\n\n\
+             00:00:06.000 --> 00:00:06.900\nword13 word14\n\n".into(),
+        ),
+        ("nothing said", vec![segment(0, 1_000, "  ")], String::new(), "WEBVTT\n\n".into())
+    ];
+
+    for (name, segments, srt, vtt) in cases {
+        assert_eq!(render_srt(&segments), srt, "SRT: {}", name);
+        assert_eq!(render_vtt(&segments), vtt, "VTT: {}", name);
+    }
+}
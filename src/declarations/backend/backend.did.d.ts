@@ -11,17 +11,25 @@ export interface DownloadChunkResponse {
   'data' : Uint8Array | number[],
   'total_size' : bigint,
 }
+export type ExportFormat = { 'Srt' : null } |
+  { 'Txt' : null } |
+  { 'Vtt' : null } |
+  { 'Json' : null };
 export interface FileArtifact {
+  'media' : [] | [MediaInfo],
   'title' : [] | [string],
+  'thumbnail' : [] | [Thumbnail],
   'owner' : Principal,
   'size' : bigint,
   'content_type' : string,
+  'speakers' : [] | [Array<Speaker>],
   'created_at' : bigint,
   'filename' : string,
   'summary' : [] | [Summary],
   'transcription' : [] | [Transcription],
   'deleted_at' : [] | [bigint],
   'visibility' : FileArtifactVisibility,
+  'waveform' : [] | [Waveform],
   'file_id' : string,
 }
 export interface FileArtifactFilter {
@@ -40,11 +48,35 @@ export type FileArtifactVisibility = { 'Private' : null } |
   { 'Public' : null };
 export type FileTypeFilter = { 'Audio' : null } |
   { 'Video' : null };
+export interface JobProgress { 'stage' : JobStage }
+export type JobStage = { 'Queued' : null } |
+  { 'Decoding' : null } |
+  { 'Transcribing' : null } |
+  { 'PostProcessing' : null } |
+  { 'Assembling' : null };
 export type JobStatus = { 'Failed' : string } |
+  { 'Cancelled' : null } |
+  { 'InProgress' : JobProgress } |
   { 'Completed' : string } |
   { 'Pending' : null };
 export type LanguageFilter = { 'English' : null } |
   { 'Indonesia' : null };
+export interface MediaInfo {
+  'size' : bigint,
+  'container' : [] | [string],
+  'duration_ms' : [] | [bigint],
+  'streams' : Array<MediaStream>,
+}
+export interface MediaStream {
+  'height' : [] | [number],
+  'kind' : StreamKind,
+  'codec' : [] | [string],
+  'channels' : [] | [number],
+  'sample_rate' : [] | [number],
+  'index' : number,
+  'width' : [] | [number],
+  'duration_ms' : [] | [bigint],
+}
 export type Result = { 'Ok' : string } |
   { 'Err' : string };
 export type Result_1 = { 'Ok' : null } |
@@ -61,32 +93,63 @@ export type SortOrderFilter = { 'Oldest' : null } |
   { 'AlphabeticalDesc' : null } |
   { 'AlphabeticalAsc' : null } |
   { 'Newest' : null };
+export interface Speaker { 'name' : string, 'label' : string }
 export interface StartUploadRequest {
   'total_chunks' : bigint,
   'content_type' : string,
   'total_size' : bigint,
   'filename' : string,
 }
+export type StreamKind = { 'Audio' : null } |
+  { 'Other' : null } |
+  { 'Video' : null };
 export interface Summary {
   'text' : string,
   'created_at' : bigint,
   'deleted_at' : [] | [bigint],
   'file_id' : string,
 }
+export interface Thumbnail {
+  'data' : Uint8Array | number[],
+  'content_type' : string,
+}
 export interface Transcription {
+  'model' : [] | [string],
   'text' : string,
   'segments' : Array<TranscriptionSegment>,
   'created_at' : bigint,
   'language' : string,
   'job_id' : string,
   'deleted_at' : [] | [bigint],
+  'options' : [] | [TranscriptionOptions],
   'file_id' : string,
 }
+export interface TranscriptionOptions {
+  'temperature' : [] | [number],
+  'initial_prompt' : [] | [string],
+  'translate' : [] | [boolean],
+  'diarize' : [] | [boolean],
+  'language' : [] | [string],
+  'max_speakers' : [] | [number],
+  'beam_size' : [] | [number],
+  'temperature_increment' : [] | [number],
+  'best_of' : [] | [number],
+}
 export interface TranscriptionSegment {
   'id' : number,
-  'end' : number,
+  'avg_logprob' : [] | [number],
+  'start_ms' : bigint,
+  'text' : string,
+  'no_speech_prob' : [] | [number],
+  'words' : [] | [Array<TranscriptionWord>],
+  'speaker' : [] | [string],
+  'end_ms' : bigint,
+}
+export interface TranscriptionWord {
+  'probability' : number,
+  'start_ms' : bigint,
   'text' : string,
-  'start' : number,
+  'end_ms' : bigint,
 }
 export interface UploadChunkRequest {
   'chunk_index' : bigint,
@@ -97,15 +160,22 @@ export interface UserFileArtifact {
   'artifact' : FileArtifact,
   'is_bookmarked' : boolean,
 }
+export interface Waveform {
+  'peaks' : Uint8Array | number[],
+  'duration_ms' : bigint,
+}
 export interface _SERVICE {
+  'cancel_transcription' : ActorMethod<[string], Result>,
   'complete_upload' : ActorMethod<[string], Result>,
   'delete_file' : ActorMethod<[string], Result>,
   'delete_file_artifact' : ActorMethod<[string], Result_1>,
   'edit_file_artifact' : ActorMethod<[FileArtifactRequest], Result_2>,
+  'export_transcription' : ActorMethod<[string, ExportFormat], Result>,
   'get_file_artifact' : ActorMethod<[string], [] | [UserFileArtifact]>,
   'get_file_chunk' : ActorMethod<[DownloadChunkRequest], Result_3>,
   'get_summary_result' : ActorMethod<[string], JobStatus>,
   'get_transcription' : ActorMethod<[string], Result>,
+  'get_transcription_events_url' : ActorMethod<[string], Result>,
   'get_transcription_result' : ActorMethod<[string], Result>,
   'get_transcription_status' : ActorMethod<[string], Result_4>,
   'get_upload_status' : ActorMethod<[string], Result_5>,
@@ -120,12 +190,17 @@ export interface _SERVICE {
   >,
   'login' : ActorMethod<[], string>,
   'logout' : ActorMethod<[], string>,
+  'rename_speaker' : ActorMethod<[string, string, string], Result>,
   'search_file_artifacts' : ActorMethod<
     [[] | [FileArtifactFilter]],
     Array<UserFileArtifact>
   >,
+  'set_transcription_signing_key' : ActorMethod<[string], Result_1>,
   'start_summarization' : ActorMethod<[string], Result>,
-  'start_transcription' : ActorMethod<[string], Result>,
+  'start_transcription' : ActorMethod<
+    [string, [] | [TranscriptionOptions]],
+    Result
+  >,
   'start_upload' : ActorMethod<[StartUploadRequest], Result>,
   'toggle_file_artifact_bookmark' : ActorMethod<[string], Result>,
   'toggle_file_artifact_visibility' : ActorMethod<[string], Result>,
@@ -7,19 +7,42 @@ export const idlFactory = ({ IDL }) => {
     'deleted_at' : IDL.Opt(IDL.Nat64),
     'file_id' : IDL.Text,
   });
+  const TranscriptionWord = IDL.Record({
+    'probability' : IDL.Float32,
+    'start_ms' : IDL.Nat64,
+    'text' : IDL.Text,
+    'end_ms' : IDL.Nat64,
+  });
   const TranscriptionSegment = IDL.Record({
     'id' : IDL.Nat32,
-    'end' : IDL.Float32,
+    'avg_logprob' : IDL.Opt(IDL.Float32),
+    'start_ms' : IDL.Nat64,
     'text' : IDL.Text,
-    'start' : IDL.Float32,
+    'no_speech_prob' : IDL.Opt(IDL.Float32),
+    'words' : IDL.Opt(IDL.Vec(TranscriptionWord)),
+    'speaker' : IDL.Opt(IDL.Text),
+    'end_ms' : IDL.Nat64,
+  });
+  const TranscriptionOptions = IDL.Record({
+    'temperature' : IDL.Opt(IDL.Float32),
+    'initial_prompt' : IDL.Opt(IDL.Text),
+    'translate' : IDL.Opt(IDL.Bool),
+    'diarize' : IDL.Opt(IDL.Bool),
+    'language' : IDL.Opt(IDL.Text),
+    'max_speakers' : IDL.Opt(IDL.Nat32),
+    'beam_size' : IDL.Opt(IDL.Nat32),
+    'temperature_increment' : IDL.Opt(IDL.Float32),
+    'best_of' : IDL.Opt(IDL.Nat32),
   });
   const Transcription = IDL.Record({
+    'model' : IDL.Opt(IDL.Text),
     'text' : IDL.Text,
     'segments' : IDL.Vec(TranscriptionSegment),
     'created_at' : IDL.Nat64,
     'language' : IDL.Text,
     'job_id' : IDL.Text,
     'deleted_at' : IDL.Opt(IDL.Nat64),
+    'options' : IDL.Opt(TranscriptionOptions),
     'file_id' : IDL.Text,
   });
   const FileArtifactRequest = IDL.Record({
@@ -32,17 +55,51 @@ export const idlFactory = ({ IDL }) => {
     'Private' : IDL.Null,
     'Public' : IDL.Null,
   });
+  const Speaker = IDL.Record({ 'name' : IDL.Text, 'label' : IDL.Text });
+  const StreamKind = IDL.Variant({
+    'Audio' : IDL.Null,
+    'Other' : IDL.Null,
+    'Video' : IDL.Null,
+  });
+  const MediaStream = IDL.Record({
+    'height' : IDL.Opt(IDL.Nat32),
+    'kind' : StreamKind,
+    'codec' : IDL.Opt(IDL.Text),
+    'channels' : IDL.Opt(IDL.Nat32),
+    'sample_rate' : IDL.Opt(IDL.Nat32),
+    'index' : IDL.Nat32,
+    'width' : IDL.Opt(IDL.Nat32),
+    'duration_ms' : IDL.Opt(IDL.Nat64),
+  });
+  const MediaInfo = IDL.Record({
+    'size' : IDL.Nat64,
+    'container' : IDL.Opt(IDL.Text),
+    'duration_ms' : IDL.Opt(IDL.Nat64),
+    'streams' : IDL.Vec(MediaStream),
+  });
+  const Thumbnail = IDL.Record({
+    'data' : IDL.Vec(IDL.Nat8),
+    'content_type' : IDL.Text,
+  });
+  const Waveform = IDL.Record({
+    'peaks' : IDL.Vec(IDL.Nat8),
+    'duration_ms' : IDL.Nat64,
+  });
   const FileArtifact = IDL.Record({
+    'media' : IDL.Opt(MediaInfo),
     'title' : IDL.Opt(IDL.Text),
+    'thumbnail' : IDL.Opt(Thumbnail),
     'owner' : IDL.Principal,
     'size' : IDL.Nat64,
     'content_type' : IDL.Text,
+    'speakers' : IDL.Opt(IDL.Vec(Speaker)),
     'created_at' : IDL.Nat64,
     'filename' : IDL.Text,
     'summary' : IDL.Opt(Summary),
     'transcription' : IDL.Opt(Transcription),
     'deleted_at' : IDL.Opt(IDL.Nat64),
     'visibility' : FileArtifactVisibility,
+    'waveform' : IDL.Opt(Waveform),
     'file_id' : IDL.Text,
   });
   const Result_2 = IDL.Variant({ 'Ok' : FileArtifact, 'Err' : IDL.Text });
@@ -63,8 +120,24 @@ export const idlFactory = ({ IDL }) => {
     'Ok' : DownloadChunkResponse,
     'Err' : IDL.Text,
   });
+  const ExportFormat = IDL.Variant({
+    'Srt' : IDL.Null,
+    'Txt' : IDL.Null,
+    'Vtt' : IDL.Null,
+    'Json' : IDL.Null,
+  });
+  const JobStage = IDL.Variant({
+    'Queued' : IDL.Null,
+    'Decoding' : IDL.Null,
+    'Transcribing' : IDL.Null,
+    'PostProcessing' : IDL.Null,
+    'Assembling' : IDL.Null,
+  });
+  const JobProgress = IDL.Record({ 'stage' : JobStage });
   const JobStatus = IDL.Variant({
     'Failed' : IDL.Text,
+    'Cancelled' : IDL.Null,
+    'InProgress' : JobProgress,
     'Completed' : IDL.Text,
     'Pending' : IDL.Null,
   });
@@ -105,10 +178,16 @@ export const idlFactory = ({ IDL }) => {
     'data' : IDL.Vec(IDL.Nat8),
   });
   return IDL.Service({
+    'cancel_transcription' : IDL.Func([IDL.Text], [Result], []),
     'complete_upload' : IDL.Func([IDL.Text], [Result], []),
     'delete_file' : IDL.Func([IDL.Text], [Result], []),
     'delete_file_artifact' : IDL.Func([IDL.Text], [Result_1], []),
     'edit_file_artifact' : IDL.Func([FileArtifactRequest], [Result_2], []),
+    'export_transcription' : IDL.Func(
+        [IDL.Text, ExportFormat],
+        [Result],
+        ['query'],
+      ),
     'get_file_artifact' : IDL.Func(
         [IDL.Text],
         [IDL.Opt(UserFileArtifact)],
@@ -117,6 +196,7 @@ export const idlFactory = ({ IDL }) => {
     'get_file_chunk' : IDL.Func([DownloadChunkRequest], [Result_3], ['query']),
     'get_summary_result' : IDL.Func([IDL.Text], [JobStatus], ['query']),
     'get_transcription' : IDL.Func([IDL.Text], [Result], ['query']),
+    'get_transcription_events_url' : IDL.Func([IDL.Text], [Result], ['query']),
     'get_transcription_result' : IDL.Func([IDL.Text], [Result], []),
     'get_transcription_status' : IDL.Func([IDL.Text], [Result_4], []),
     'get_upload_status' : IDL.Func([IDL.Text], [Result_5], ['query']),
@@ -133,13 +213,19 @@ export const idlFactory = ({ IDL }) => {
       ),
     'login' : IDL.Func([], [IDL.Text], []),
     'logout' : IDL.Func([], [IDL.Text], []),
+    'rename_speaker' : IDL.Func([IDL.Text, IDL.Text, IDL.Text], [Result], []),
     'search_file_artifacts' : IDL.Func(
         [IDL.Opt(FileArtifactFilter)],
         [IDL.Vec(UserFileArtifact)],
         ['query'],
       ),
+    'set_transcription_signing_key' : IDL.Func([IDL.Text], [Result_1], []),
     'start_summarization' : IDL.Func([IDL.Text], [Result], []),
-    'start_transcription' : IDL.Func([IDL.Text], [Result], []),
+    'start_transcription' : IDL.Func(
+        [IDL.Text, IDL.Opt(TranscriptionOptions)],
+        [Result],
+        [],
+      ),
     'start_upload' : IDL.Func([StartUploadRequest], [Result], []),
     'toggle_file_artifact_bookmark' : IDL.Func([IDL.Text], [Result], []),
     'toggle_file_artifact_visibility' : IDL.Func([IDL.Text], [Result], []),
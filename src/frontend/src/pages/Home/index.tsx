@@ -14,6 +14,15 @@ import { useSnackbarStore } from "@/store/useSnackbarStore";
 import { useNavigate } from "react-router-dom";
 import { useRecentWorkStore } from "@/store/useRecentWorkStore";
 
+// Stages a transcription job goes through, in order
+const TRANSCRIPTION_STAGES = [
+   "Queued",
+   "Assembling",
+   "Decoding",
+   "Transcribing",
+   "PostProcessing",
+];
+
 const Home = () => {
    const [file, setFile] = useState<File | null>(null);
    const [uploading, setUploading] = useState(false);
@@ -203,7 +212,7 @@ const Home = () => {
          console.log("Starting transcription...");
          setUploadProgress(80);
 
-         const startTranscribeJob = await backend.start_transcription(fileId);
+         const startTranscribeJob = await backend.start_transcription(fileId, []);
          if ("Err" in startTranscribeJob) {
             throw new Error(startTranscribeJob.Err);
          }
@@ -223,7 +232,14 @@ const Home = () => {
             if ("Err" in statusResult) throw new Error(statusResult.Err);
             const status = statusResult.Ok;
 
-            if ("Pending" in status) {
+            if ("Pending" in status || "InProgress" in status) {
+               if ("InProgress" in status) {
+                  // Transcription fills the 80-90% part of the bar, a stage at a time
+                  const stage = TRANSCRIPTION_STAGES.findIndex(
+                     (name) => name in status.InProgress.stage
+                  );
+                  setUploadProgress(80 + (stage * 10) / TRANSCRIPTION_STAGES.length);
+               }
                console.log("Transcription in progress...");
                await new Promise((resolve) =>
                   setTimeout(resolve, pollInterval)
@@ -241,6 +257,10 @@ const Home = () => {
             if ("Failed" in status) {
                throw new Error(`Transcription failed: ${status.Failed}`);
             }
+
+            if ("Cancelled" in status) {
+               throw new Error("Transcription was cancelled");
+            }
          }
 
          // Fetch transcription result
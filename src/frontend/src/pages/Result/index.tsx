@@ -145,6 +145,10 @@ const Result = () => {
       );
    }, [work?.artifact.transcription, searchTerm]);
 
+   // Speaker labels renamed by the owner, falling back to the raw label
+   const speakerName = (label: string) =>
+      work?.artifact.speakers[0]?.find((s) => s.label === label)?.name ?? label;
+
    // Handle search input change
    const handleSearchChange = (e: React.ChangeEvent<HTMLInputElement>) => {
       const value = e.target.value;
@@ -690,7 +694,7 @@ const Result = () => {
                                    : work?.artifact.transcription[0]
                                         ?.segments || []
                                 ).map((s) => {
-                                   const startTime = formatTime(s.start);
+                                   const startTime = formatTime(Number(s.start_ms) / 1000);
 
                                    return (
                                       <Box
@@ -703,13 +707,18 @@ const Result = () => {
                                             <button
                                                className="text-primary hover:underline cursor-pointer p-0 min-w-[50px] text-left"
                                                onClick={() =>
-                                                  handleSeekTo(s.start)
+                                                  handleSeekTo(Number(s.start_ms) / 1000)
                                                }
                                             >
                                                {startTime}
                                             </button>
                                          </Tooltip>
                                          <p className="flex-1 text-sm leading-relaxed">
+                                            {s.speaker[0] && (
+                                               <span className="font-semibold mr-2">
+                                                  {speakerName(s.speaker[0])}:
+                                               </span>
+                                            )}
                                             <HighlightText
                                                text={s.text}
                                                searchTerm={searchTerm}
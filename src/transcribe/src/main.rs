@@ -1,166 +1,1256 @@
-use axum::{ extract::{ Multipart, Path }, routing::{ post, get }, Router, Json };
-use serde::{ Serialize, Deserialize };
-use std::{ collections::HashMap, net::SocketAddr, time::Duration, sync::{ Arc, Mutex } };
+use axum::{
+    extract::{ multipart::Field, rejection::JsonRejection, FromRef, Multipart, Path, Query, State },
+    Extension,
+    http::{ header, StatusCode },
+    middleware,
+    response::{ sse::{ Event, KeepAlive, Sse }, IntoResponse, Response },
+    routing::{ delete, post, get },
+    Router,
+    Json,
+};
+use std::{
+    collections::{ HashMap, VecDeque },
+    net::SocketAddr,
+    time::{ Duration, Instant },
+    sync::{ atomic::{ AtomicBool, Ordering }, Arc, Mutex },
+};
+use std::io::Write;
+use std::panic::{ self, AssertUnwindSafe };
+use futures_util::{ stream, Stream };
+use tokio::sync::broadcast::{ self, error::RecvError };
 use tower_http::timeout::TimeoutLayer;
-use once_cell::sync::Lazy;
-use tokio::task;
 
 mod modules;
+#[cfg(test)]
+mod tests;
 
 use modules::*;
 
-#[derive(Serialize, Deserialize, Clone)]
-#[serde(tag = "status", content = "data")]
-enum JobStatus {
-    Pending,
-    Completed(String),
-    Failed(String),
+/// What the handlers and the workers share, built by `serve` and by each test on its own
+pub struct App {
+    config: Config,
+    store: JobStore,
+    jobs: Mutex<HashMap<String, JobRecord>>,
+    /// Held while a job is written to or removed from the journal, one job at a time
+    journal: Mutex<()>,
+    /// Ids of the jobs waiting for a worker, in the order they will start
+    queue: Mutex<VecDeque<String>>,
+    /// Flags checked by running jobs, set to stop them
+    cancel_flags: Mutex<HashMap<String, Arc<AtomicBool>>>,
+    /// Event channels of the running jobs that are listened to or have decoded segments,
+    /// dropped once the job finishes
+    events: Mutex<HashMap<String, JobChannel>>,
+    workers: WorkerPool,
 }
 
-static JOBS: Lazy<Mutex<HashMap<String, JobStatus>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+impl App {
+    pub fn new(config: Config, store: JobStore) -> Self {
+        let workers = WorkerPool::new(config.workers, config.queue_size);
 
-static UPLOAD_SESSIONS: Lazy<Arc<Mutex<HashMap<String, Vec<Vec<u8>>>>>> = Lazy::new(||
-    Arc::new(Mutex::new(HashMap::new()))
-);
+        App {
+            config,
+            store,
+            jobs: Mutex::new(HashMap::new()),
+            journal: Mutex::new(()),
+            queue: Mutex::new(VecDeque::new()),
+            cancel_flags: Mutex::new(HashMap::new()),
+            events: Mutex::new(HashMap::new()),
+            workers,
+        }
+    }
+}
+
+/// State of the router, handlers that do not need the engine take the `App` alone
+pub struct AppState<T> {
+    app: Arc<App>,
+    engine: Arc<T>,
+}
+
+impl<T> Clone for AppState<T> {
+    fn clone(&self) -> Self {
+        AppState { app: Arc::clone(&self.app), engine: Arc::clone(&self.engine) }
+    }
+}
+
+impl<T> FromRef<AppState<T>> for Arc<App> {
+    fn from_ref(state: &AppState<T>) -> Self {
+        Arc::clone(&state.app)
+    }
+}
+
+/// How often expired jobs and abandoned upload sessions are looked for
+const GC_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Events a slow listener may fall behind by before it misses some
+const JOB_EVENTS_CAPACITY: usize = 256;
 
 #[tokio::main]
 async fn main() {
-    let app = Router::new()
+    let config = Config::load().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+
+    let result = match config.engine {
+        EngineKind::Whisper => {
+            let engine = WhisperTranscriber::new(&config);
+            run(config, Arc::new(engine)).await
+        }
+        EngineKind::Mock => {
+            let script = match &config.mock_script {
+                Some(path) => MockScript::read(path),
+                None => Ok(MockScript::default()),
+            };
+            match script {
+                Ok(script) => {
+                    let engine = MockTranscriber::new(script, &config.default_model);
+                    run(config, Arc::new(engine)).await
+                }
+                Err(e) => Err(e),
+            }
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run<T: Transcriber>(config: Config, engine: Arc<T>) -> anyhow::Result<()> {
+    match config.command.clone() {
+        Command::Serve => {
+            serve(config, engine).await;
+            Ok(())
+        }
+        // Whisper blocks, so the offline commands run off the async runtime
+        Command::Transcribe(command) => {
+            tokio::task
+                ::spawn_blocking(move || cli::transcribe_files(&*engine, &config, command)).await
+                .unwrap()
+        }
+        Command::Probe { file } => {
+            tokio::task::spawn_blocking(move || cli::probe_file(&config, &file)).await.unwrap()
+        }
+    }
+}
+
+/// Run the HTTP service until the process is stopped
+async fn serve<T: Transcriber>(config: Config, engine: Arc<T>) {
+    // Load the default model before accepting any job
+    engine.load_model(engine.default_model()).expect("Failed to load the default model");
+    println!(
+        "Loaded model {} with the {:?} engine ({} workers, {} threads per job)",
+        engine.default_model(),
+        config.engine,
+        config.workers,
+        config.threads_per_job
+    );
+
+    let store = JobStore::open(&config.data_dir).expect("Failed to open job store");
+    let app = Arc::new(App::new(config, store));
+    restore_state(&app, &engine);
+    tokio::spawn(collect_garbage_periodically(Arc::clone(&app)));
+
+    if app.config.signing_key.is_none() {
+        eprintln!("No signing key configured, requests are not authenticated");
+    }
+
+    let routes = router(app, engine).layer(TimeoutLayer::new(Duration::from_secs(120)));
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
+    println!("Server running at http://{}", addr);
+
+    axum::serve(tokio::net::TcpListener::bind(addr).await.unwrap(), routes).await.unwrap();
+}
+
+/// Routes of the API, only probing, finalizing and listing models need the engine
+///
+/// With the configured signing key every request has to be signed, see
+/// `auth::require_signature`. Event streams are opened with a token instead, see
+/// `auth::require_event_token`.
+fn router<T: Transcriber>(app: Arc<App>, engine: Arc<T>) -> Router {
+    let signing_key = app.config.signing_key.clone();
+
+    let api = Router::new()
+        .route("/sessions", post(create_session))
+        .route("/sessions/{session_id}", get(session_status))
         .route("/upload_chunk", post(upload_chunk))
-        .route("/finalize_upload", post(finalize_upload))
+        .route("/probe", post(probe_session::<T>))
+        .route("/finalize_upload", post(finalize_upload::<T>))
         .route("/status/{job_id}", get(check_job_status))
         .route("/result/{job_id}", get(transcription_result))
-        .layer(TimeoutLayer::new(Duration::from_secs(120)));
+        .route("/jobs/{job_id}", delete(delete_job))
+        // Same as DELETE, for clients limited to GET and POST such as canister HTTP outcalls
+        .route("/jobs/{job_id}/cancel", post(delete_job))
+        .route("/models", get(list_models::<T>))
+        .with_state(AppState { app: Arc::clone(&app), engine });
 
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
-    println!("Server running at http://{}", addr);
+    // Browsers follow jobs with `EventSource`, which cannot sign, so events take a token
+    let events = Router::new().route("/events/{job_id}", get(job_events)).with_state(app);
+
+    let Some(key) = signing_key else {
+        return api.merge(events);
+    };
+
+    let key = Arc::new(auth::SigningKey::new(key));
+    api.layer(middleware::from_fn_with_state(Arc::clone(&key), auth::require_signature)).merge(
+        events.route_layer(middleware::from_fn_with_state(key, auth::require_event_token))
+    )
+}
+
+/// Reload journaled sessions and jobs, resuming interrupted jobs whose chunks are still on disk
+fn restore_state<T: Transcriber>(app: &Arc<App>, engine: &Arc<T>) {
+    let jobs = app.store.load_jobs().unwrap_or_else(|e| {
+        eprintln!("Failed to restore jobs: {}", e);
+        vec![]
+    });
+
+    for job in jobs {
+        let job_id = job.job_id.clone();
+        let session_id = job.session_id.clone();
+        let model = job.model.clone().unwrap_or_else(|| engine.default_model().to_string());
+        let options = job.options.clone();
+        let interrupted = !job.status.is_finished();
+
+        app.jobs.lock().unwrap().insert(job_id.clone(), job);
+
+        if !interrupted {
+            continue;
+        }
+
+        let resumable = app.store.has_session(&session_id);
+        if resumable {
+            println!("Resuming interrupted job {}", job_id);
+            if let Err(e) = spawn_job(app, engine, job_id.clone(), session_id, model, options) {
+                update_job(app, &job_id, JobStatus::Failed(e.to_string()));
+                notify_callback(app, &job_id);
+            }
+        } else {
+            let status = JobStatus::Failed("Interrupted by service restart".to_string());
+            update_job(app, &job_id, status);
+            notify_callback(app, &job_id);
+        }
+    }
+}
+
+/// Run `collect_garbage` every `GC_INTERVAL` for as long as the service is up
+async fn collect_garbage_periodically(app: Arc<App>) {
+    let mut interval = tokio::time::interval(GC_INTERVAL);
+    loop {
+        interval.tick().await;
+        let app = Arc::clone(&app);
+        if let Err(e) = tokio::task::spawn_blocking(move || collect_garbage(&app)).await {
+            eprintln!("Garbage collection failed: {}", e);
+        }
+    }
+}
+
+/// Drop finished jobs past their TTL, and upload sessions that were never finalized
+fn collect_garbage(app: &App) {
+    let now = unix_now();
+
+    let expired: Vec<String> = app.jobs.lock()
+        .unwrap()
+        .values()
+        .filter(|job| job.expires_at(app.config.job_ttl_secs).is_some_and(|at| at <= now))
+        .map(|job| job.job_id.clone())
+        .collect();
+
+    for job_id in expired {
+        forget_job(app, &job_id);
+    }
+
+    let sessions = app.store.list_sessions().unwrap_or_else(|e| {
+        eprintln!("Failed to list upload sessions: {}", e);
+        vec![]
+    });
+
+    for session in sessions {
+        // A finalized session belongs to its job, which removes it when it is done
+        let finalized = active_job(&app.jobs.lock().unwrap(), &session.session_id).is_some();
+        if finalized || session.last_activity() + app.config.session_ttl_secs > now {
+            continue;
+        }
+
+        if let Err(e) = app.store.remove_session(&session.session_id) {
+            eprintln!("Failed to remove abandoned session {}: {}", session.session_id, e);
+        }
+    }
+}
+
+/// Job still working on a session, a session can be uploaded again once its last job is done
+fn active_job(jobs: &HashMap<String, JobRecord>, session_id: &str) -> Option<String> {
+    jobs.values()
+        .find(|job| job.session_id == session_id && !job.status.is_finished())
+        .map(|job| job.job_id.clone())
+}
 
-    axum::serve(tokio::net::TcpListener::bind(addr).await.unwrap(), app).await.unwrap();
+/// Job a session was already finalized as, unless it was uploaded again since that job settled
+fn finalized_job(app: &App, session_id: &str) -> Result<Option<String>, ApiError> {
+    let latest = app.jobs.lock()
+        .unwrap()
+        .values()
+        .filter(|job| job.session_id == session_id)
+        .max_by_key(|job| job.created_at)
+        .cloned();
+    let Some(job) = latest else {
+        return Ok(None);
+    };
+    if !job.status.is_finished() {
+        return Ok(Some(job.job_id));
+    }
+
+    // A settled job removes its session, one that was written to after the job started is a
+    // new upload
+    let session = app.store.session(session_id).map_err(|e| {
+        ApiError::Internal(format!("Failed to read session {}: {}", session_id, e))
+    })?;
+    let uploaded_again = session.is_some_and(|session| session.last_activity() > job.created_at);
+
+    Ok((!uploaded_again).then_some(job.job_id))
+}
+
+/// Update a job in memory and in the journal
+///
+/// Writes to disk, async callers run it with `spawn_blocking`.
+fn update_job(app: &App, job_id: &str, status: JobStatus) {
+    {
+        let mut jobs = app.jobs.lock().unwrap();
+
+        // A finished job stays as it is, a worker noticing a cancellation late must not revive it
+        let Some(job) = jobs.get_mut(job_id).filter(|job| !job.status.is_finished()) else {
+            return;
+        };
+        job.set_status(status);
+        publish_event(app, job_id, JobEvent::Status(job.status.clone()));
+    }
+
+    persist_job(app, job_id);
+}
+
+/// Write the current state of a job to the journal, without holding the jobs lock meanwhile
+///
+/// The record is read once the journal is free rather than passed in, so a write that had to
+/// wait for another never puts an older state of the job back.
+fn persist_job(app: &App, job_id: &str) {
+    let _journal = app.journal.lock().unwrap();
+
+    let Some(job) = app.jobs.lock().unwrap().get(job_id).cloned() else {
+        return;
+    };
+    if let Err(e) = app.store.save_job(&job) {
+        eprintln!("Failed to persist job {}: {}", job_id, e);
+    }
+}
+
+/// Drop a job from memory and from the journal
+///
+/// Holds the journal like `persist_job`, so a write still in flight cannot bring it back.
+fn forget_job(app: &App, job_id: &str) {
+    let _journal = app.journal.lock().unwrap();
+
+    app.jobs.lock().unwrap().remove(job_id);
+    if let Err(e) = app.store.remove_job(job_id) {
+        eprintln!("Failed to remove job {}: {}", job_id, e);
+    }
+}
+
+/// Push an event to the listeners of a job, if it has any
+fn publish_event(app: &App, job_id: &str, event: JobEvent) {
+    let mut channels = app.events.lock().unwrap();
+
+    // The channel goes away with the last event, which ends the listeners' streams after it
+    let sender = match &event {
+        JobEvent::Status(status) if status.is_finished() => {
+            channels.remove(job_id).map(|channel| channel.sender)
+        }
+        _ => channels.get(job_id).map(|channel| channel.sender.clone()),
+    };
+    if let Some(sender) = sender {
+        // Nobody listening any more is fine
+        let _ = sender.send(event);
+    }
+}
+
+/// Keep a decoded segment for listeners joining later, and push it to the current ones
+fn publish_segment(app: &App, job_id: &str, segment: TranscriptionSegment) {
+    // A worker still decoding after a cancellation must not open a channel for the dead job
+    let jobs = app.jobs.lock().unwrap();
+    if !jobs.get(job_id).is_some_and(|job| !job.status.is_finished()) {
+        return;
+    }
+
+    let mut channels = app.events.lock().unwrap();
+    let channel = channels
+        .entry(job_id.to_string())
+        .or_insert_with(|| JobChannel::new(JOB_EVENTS_CAPACITY));
+    channel.segments.push(segment.clone());
+    let _ = channel.sender.send(JobEvent::Segment(segment));
+}
+
+/// Declare an upload, sending the same declaration again is a no-op so a client can retry
+pub async fn create_session(
+    State(app): State<Arc<App>>,
+    request: Result<Json<CreateSessionRequest>, JsonRejection>
+) -> Result<Json<SessionStatusResponse>, ApiError> {
+    let Json(request) = request?;
+
+    if !is_valid_id(&request.session_id) {
+        return Err(ApiError::BadRequest(format!("Invalid session id: {}", request.session_id)));
+    }
+    if request.total_chunks == 0 {
+        return Err(ApiError::BadRequest("total_chunks must be at least 1".to_string()));
+    }
+    if request.total_size > app.config.max_session_bytes {
+        return Err(
+            ApiError::PayloadTooLarge(
+                format!(
+                    "Upload of {} bytes is larger than the {} MB limit",
+                    request.total_size,
+                    app.config.max_session_bytes / (1024 * 1024)
+                )
+            )
+        );
+    }
+    let sha256 = request.sha256.map(|sha| sha.to_ascii_lowercase());
+    if let Some(sha) = &sha256 {
+        if sha.len() != 64 || !sha.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ApiError::BadRequest(format!("Invalid sha256: {}", sha)));
+        }
+    }
+
+    let store_error = |e: std::io::Error| {
+        ApiError::Internal(format!("Failed to open session: {}", e))
+    };
+
+    // Declaring an existing session gives it back as it is, so a retry does not reset it
+    let now = unix_now();
+    let record = app.store.create_session(SessionRecord {
+        session_id: request.session_id.clone(),
+        total_chunks: Some(request.total_chunks),
+        total_size: Some(request.total_size),
+        sha256: sha256.clone(),
+        received_chunks: vec![],
+        created_at: now,
+        updated_at: now,
+    }).map_err(store_error)?;
+
+    let same =
+        record.total_chunks == Some(request.total_chunks) &&
+        record.total_size == Some(request.total_size) &&
+        record.sha256 == sha256;
+    if !same {
+        return Err(
+            ApiError::Conflict(format!("Session {} was declared differently", request.session_id))
+        );
+    }
+
+    session_status_response(&app, &record).map(Json)
+}
+
+/// Chunks received so far and the ones still missing
+pub async fn session_status(
+    State(app): State<Arc<App>>,
+    Path(session_id): Path<String>
+) -> Result<Json<SessionStatusResponse>, ApiError> {
+    if !is_valid_id(&session_id) {
+        return Err(ApiError::BadRequest(format!("Invalid session id: {}", session_id)));
+    }
+
+    let record = app.store.session(&session_id)
+        .map_err(|e| ApiError::Internal(format!("Failed to read session {}: {}", session_id, e)))?
+        .ok_or_else(|| ApiError::NotFound(format!("Session {} not found", session_id)))?;
+
+    session_status_response(&app, &record).map(Json)
 }
 
-pub async fn upload_chunk(mut multipart: Multipart) -> String {
-    let mut session_id = String::new();
-    let mut chunk_index = 0;
-    let mut chunk_data = Vec::new();
+fn session_status_response(
+    app: &App,
+    record: &SessionRecord
+) -> Result<SessionStatusResponse, ApiError> {
+    let received_size = app.store.session_size(record, None).map_err(|e| {
+        ApiError::Internal(format!("Failed to read session {}: {}", record.session_id, e))
+    })?;
 
-    while let Some(field) = multipart.next_field().await.unwrap_or(None) {
+    Ok(SessionStatusResponse {
+        session_id: record.session_id.clone(),
+        total_chunks: record.total_chunks,
+        total_size: record.total_size,
+        received_size,
+        received_chunks: record.received_chunks.clone(),
+        missing_chunks: record.missing_chunks(),
+    })
+}
+
+/// Store one chunk of an upload, written to the session's spool directory as it arrives
+///
+/// `session_id` and `chunk_index` have to come before `file`, so the chunk can be streamed
+/// to disk without buffering it first.
+pub async fn upload_chunk(
+    State(app): State<Arc<App>>,
+    signature: Option<Extension<auth::SignedBody>>,
+    mut multipart: Multipart
+) -> Result<String, ApiError> {
+    let mut session_id = None;
+    let mut chunk_index = None;
+    let mut received = None;
+
+    while let Some(field) = multipart.next_field().await? {
         match field.name() {
             Some("session_id") => {
-                session_id = field.text().await.unwrap();
+                let id = field.text().await?;
+                if !is_valid_id(&id) {
+                    return Err(ApiError::BadRequest(format!("Invalid session id: {}", id)));
+                }
+                session_id = Some(id);
             }
             Some("chunk_index") => {
-                chunk_index = field.text().await.unwrap().parse().unwrap();
+                let text = field.text().await?;
+                let index = text
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| ApiError::BadRequest(format!("Invalid chunk index: {}", text)))?;
+                chunk_index = Some(index);
             }
             Some("file") => {
-                let mut f = field;
-                while let Ok(Some(chunk)) = f.chunk().await {
-                    chunk_data.extend_from_slice(&chunk);
-                }
+                let (Some(session_id), Some(chunk_index)) = (&session_id, chunk_index) else {
+                    return Err(
+                        ApiError::BadRequest(
+                            "session_id and chunk_index must come before file".to_string()
+                        )
+                    );
+                };
+
+                // The signature headers were checked before the handler ran, but whether the
+                // body is genuine is only known once it is read. Until then a session that
+                // cannot take the chunk is refused like a forged request, so nothing is
+                // learned about the sessions from a chunk that was never signed.
+                let record = writable_session(&app, session_id, chunk_index).map_err(|e| {
+                    match (&signature, e) {
+                        (Some(_), ApiError::NotFound(_) | ApiError::Conflict(_)) => {
+                            auth::request_rejected()
+                        }
+                        (_, e) => e,
+                    }
+                })?;
+
+                // A chunk that fails half way is deleted with its spool file
+                received = Some(spool_chunk(&app, field, &record, chunk_index).await?);
             }
             _ => {}
         }
     }
 
-    let mut sessions = UPLOAD_SESSIONS.lock().unwrap();
-    let entry = sessions.entry(session_id.clone()).or_default();
-    if entry.len() <= chunk_index {
-        entry.resize(chunk_index + 1, Vec::new());
+    let session_id = session_id.ok_or_else(|| {
+        ApiError::BadRequest("Missing session_id field".to_string())
+    })?;
+    let chunk_index = chunk_index.ok_or_else(|| {
+        ApiError::BadRequest("Missing chunk_index field".to_string())
+    })?;
+    let (chunk, size) = received.ok_or_else(|| {
+        ApiError::BadRequest("Missing file field".to_string())
+    })?;
+
+    // The body is only known to match the digest the signature covers once it was all read
+    if let Some(Extension(signature)) = &signature {
+        signature.verify().await?;
     }
-    entry[chunk_index] = chunk_data;
 
-    format!("Chunk {} for session {} uploaded", chunk_index, session_id)
+    app.store.commit_chunk(&session_id, chunk_index, chunk).map_err(|e| {
+        ApiError::Internal(
+            format!("Failed to store chunk {} for session {}: {}", chunk_index, session_id, e)
+        )
+    })?;
+
+    Ok(format!("Chunk {} for session {} uploaded ({} bytes)", chunk_index, session_id, size))
 }
 
-pub async fn finalize_upload(Json(data): Json<serde_json::Value>) -> Json<UploadResponse> {
-    let session_id = data["session_id"].as_str().unwrap().to_string();
+/// Session a chunk is sent for, as long as it was declared and not finalized yet
+fn writable_session(
+    app: &App,
+    session_id: &str,
+    chunk_index: usize
+) -> Result<SessionRecord, ApiError> {
+    if active_job(&app.jobs.lock().unwrap(), session_id).is_some() {
+        return Err(ApiError::Conflict(format!("Session {} is already finalized", session_id)));
+    }
+
+    let record = app.store.session(session_id)
+        .map_err(|e| ApiError::Internal(format!("Failed to read session {}: {}", session_id, e)))?
+        .ok_or_else(|| {
+            ApiError::NotFound(
+                format!("Session {} not found, declare it with POST /sessions first", session_id)
+            )
+        })?;
+
+    if let Some(total_chunks) = record.total_chunks {
+        if chunk_index >= total_chunks {
+            return Err(
+                ApiError::BadRequest(
+                    format!("Chunk index {} is out of range for {} chunks", chunk_index, total_chunks)
+                )
+            );
+        }
+    }
+
+    Ok(record)
+}
+
+/// Write a chunk to its spool file piece by piece, stopping at the declared or maximum size
+async fn spool_chunk(
+    app: &App,
+    mut field: Field<'_>,
+    record: &SessionRecord,
+    chunk_index: usize
+) -> Result<(tempfile::NamedTempFile, u64), ApiError> {
+    let session_id = &record.session_id;
+    let store_error = |e: std::io::Error| {
+        ApiError::Internal(
+            format!("Failed to store chunk {} for session {}: {}", chunk_index, session_id, e)
+        )
+    };
+
+    // A chunk sent again replaces the old one, so it does not count twice
+    let used = app.store.session_size(record, Some(chunk_index)).map_err(store_error)?;
+    let limit = record.total_size.unwrap_or(u64::MAX).min(app.config.max_session_bytes);
+    let allowed = limit.saturating_sub(used);
+
+    let mut file = app.store.create_chunk(session_id, chunk_index).map_err(store_error)?;
+    let mut written = 0u64;
+    while let Some(piece) = field.chunk().await? {
+        written += piece.len() as u64;
+        if written > allowed {
+            return Err(
+                ApiError::PayloadTooLarge(
+                    format!("Session {} is larger than its {} byte limit", session_id, limit)
+                )
+            );
+        }
+        file.write_all(&piece).map_err(store_error)?;
+    }
+    file.as_file().sync_all().map_err(store_error)?;
+
+    Ok((file, written))
+}
+
+/// Container, duration and streams of a fully uploaded session, before it is transcribed
+pub async fn probe_session<T: Transcriber>(
+    State(AppState { app, engine }): State<AppState<T>>,
+    request: Result<Json<ProbeRequest>, JsonRejection>
+) -> Result<Json<audio::MediaInfo>, ApiError> {
+    let Json(request) = request?;
+    let session_id = request.session_id;
+    if !is_valid_id(&session_id) {
+        return Err(ApiError::BadRequest(format!("Invalid session id: {}", session_id)));
+    }
+
+    // Reads the whole upload, like finalizing does
+    tokio::task
+        ::spawn_blocking(move || {
+            check_session_complete(&app, &session_id)?;
+
+            let media = app.store.assemble_session_copy(&session_id)
+                .map_err(|e| {
+                    ApiError::Internal(format!("Failed to assemble session {}: {}", session_id, e))
+                })?
+                .ok_or_else(|| ApiError::NotFound(format!("Session {} not found", session_id)))?;
+
+            engine.probe(media.path()).map_err(|e| ApiError::UnsupportedMedia(e.to_string()))
+        }).await
+        .map_err(|e| ApiError::Internal(format!("Probe failed: {}", e)))?
+        .map(Json)
+}
+
+pub async fn finalize_upload<T: Transcriber>(
+    State(AppState { app, engine }): State<AppState<T>>,
+    data: Result<Json<serde_json::Value>, JsonRejection>
+) -> Result<Json<UploadResponse>, ApiError> {
+    let Json(data) = data?;
+
+    let session_id = data["session_id"]
+        .as_str()
+        .ok_or_else(|| ApiError::BadRequest("Missing session_id field".to_string()))?
+        .to_string();
+    if !is_valid_id(&session_id) {
+        return Err(ApiError::BadRequest(format!("Invalid session id: {}", session_id)));
+    }
+
+    let requested_model = data["model"].as_str().map(|m| m.to_string());
+    let options = parse_options(&data["options"]).map_err(ApiError::BadRequest)?;
+    let callback = parse_callback(&data).map_err(ApiError::BadRequest)?;
+
+    // Sent again, such as by a caller retrying after a lost reply, it names the same job
+    if let Some(job_id) = finalized_job(&app, &session_id)? {
+        return Ok(job_started(&job_id));
+    }
+
+    // Resolving the model reads the models directory and hashing reads the whole upload,
+    // so both run off the async runtime
+    let checking_app = Arc::clone(&app);
+    let checking_engine = Arc::clone(&engine);
+    let checked_session_id = session_id.clone();
+    let model = tokio::task
+        ::spawn_blocking(move || {
+            let model = checking_engine
+                .resolve_model(requested_model.as_deref())
+                .map_err(ApiError::BadRequest)?;
+            check_session_complete(&checking_app, &checked_session_id)?;
+            Ok::<_, ApiError>(model)
+        }).await
+        .map_err(|e| ApiError::Internal(format!("Session check failed: {}", e)))??;
+
     let job_id = uuid::Uuid::new_v4().to_string();
-    let job_id_clone = job_id.clone();
 
     {
-        let mut jobs = JOBS.lock().unwrap();
-        jobs.insert(job_id.clone(), JobStatus::Pending);
+        // Checked under the jobs lock so two finalize calls cannot both start a job
+        let mut jobs = app.jobs.lock().unwrap();
+        if let Some(job_id) = active_job(&jobs, &session_id) {
+            return Ok(job_started(&job_id));
+        }
+
+        let job = JobRecord::new(
+            job_id.clone(),
+            session_id.clone(),
+            model.clone(),
+            options.clone(),
+            callback
+        );
+        jobs.insert(job_id.clone(), job);
     }
 
-    task::spawn(async move {
-        // Take chunks out while holding the lock, then drop lock
-        let chunks = {
-            let mut sessions = UPLOAD_SESSIONS.lock().unwrap();
-            sessions.remove(&session_id)
-        };
+    // Journaling and queueing write to disk, off the async runtime and the jobs lock
+    let started_job_id = job_id.clone();
+    let submitted = tokio::task
+        ::spawn_blocking(move || {
+            persist_job(&app, &started_job_id);
+            let submitted = spawn_job(
+                &app,
+                &engine,
+                started_job_id.clone(),
+                session_id,
+                model,
+                options
+            );
+            if submitted.is_err() {
+                // Nothing ran, so the session can be finalized again once a worker frees up
+                forget_job(&app, &started_job_id);
+            }
+            submitted
+        }).await
+        .map_err(|e| ApiError::Internal(format!("Starting job failed: {}", e)))?;
 
-        if chunks.is_none() {
-            let mut jobs = JOBS.lock().unwrap();
-            jobs.insert(job_id_clone, JobStatus::Failed("No chunks found".to_string()));
-            return;
+    if let Err(e) = submitted {
+        return Err(match e {
+            SubmitError::QueueFull => ApiError::Unavailable(e.to_string()),
+            SubmitError::ShutDown => ApiError::Internal(e.to_string()),
+        });
+    }
+
+    Ok(job_started(&job_id))
+}
+
+fn job_started(job_id: &str) -> Json<UploadResponse> {
+    Json(UploadResponse {
+        message: format!("Job started with ID: {}", job_id),
+    })
+}
+
+/// A session can only be transcribed once all its declared chunks arrived intact
+fn check_session_complete(app: &App, session_id: &str) -> Result<(), ApiError> {
+    let read_error = |e: std::io::Error| {
+        ApiError::Internal(format!("Failed to read session {}: {}", session_id, e))
+    };
+
+    let record = app.store.session(session_id)
+        .map_err(read_error)?
+        .ok_or_else(|| ApiError::NotFound(format!("Session {} not found", session_id)))?;
+
+    let missing = record.missing_chunks();
+    if !missing.is_empty() {
+        let missing: Vec<String> = missing.iter().map(|i| i.to_string()).collect();
+        return Err(
+            ApiError::Conflict(
+                format!("Session {} is missing chunks {}", session_id, missing.join(", "))
+            )
+        );
+    }
+
+    if let Some(total_size) = record.total_size {
+        let received = app.store.session_size(&record, None).map_err(read_error)?;
+        if received != total_size {
+            return Err(
+                ApiError::Conflict(
+                    format!(
+                        "Session {} has {} bytes, {} were declared",
+                        session_id,
+                        received,
+                        total_size
+                    )
+                )
+            );
+        }
+    }
+
+    if let Some(expected) = &record.sha256 {
+        let actual = app.store.session_checksum(&record).map_err(read_error)?;
+        if actual != *expected {
+            return Err(
+                ApiError::ChecksumMismatch(
+                    format!("Session {} does not match its sha256, upload it again", session_id)
+                )
+            );
         }
+    }
+
+    Ok(())
+}
+
+/// Move a job to the next stage, journaled so a restart shows how far it got
+fn set_stage(app: &App, job_id: &str, stage: JobStage) {
+    update_job(app, job_id, JobStatus::InProgress(JobProgress::new(stage)));
+}
+
+/// Progress within the current stage, kept in memory only as it changes many times a second
+fn set_stage_fraction(app: &App, job_id: &str, fraction: f32, eta_secs: Option<u64>) {
+    let mut jobs = app.jobs.lock().unwrap();
+
+    if let Some(JobStatus::InProgress(progress)) = jobs.get_mut(job_id).map(|job| &mut job.status) {
+        progress.set_stage_fraction(fraction, eta_secs);
+        publish_event(app, job_id, JobEvent::Status(JobStatus::InProgress(progress.clone())));
+    }
+}
+
+/// Options are optional in the request, a missing or null value means whisper's defaults
+fn parse_options(value: &serde_json::Value) -> Result<TranscriptionOptions, String> {
+    if value.is_null() {
+        return Ok(TranscriptionOptions::default());
+    }
+
+    let options: TranscriptionOptions = serde_json
+        ::from_value(value.clone())
+        .map_err(|e| format!("Invalid options: {}", e))?;
+    options.validate()?;
+
+    Ok(options)
+}
 
-        let combined: Vec<u8> = chunks.unwrap().into_iter().flatten().collect();
+/// Callback settings are optional, without a URL the job has to be polled
+fn parse_callback(data: &serde_json::Value) -> Result<Option<JobCallback>, String> {
+    let Some(url) = data["callback_url"].as_str() else {
+        return Ok(None);
+    };
 
-        if combined.is_empty() {
-            let mut jobs = JOBS.lock().unwrap();
-            jobs.insert(job_id_clone, JobStatus::Failed("No data after combining".to_string()));
+    let callback = JobCallback {
+        url: url.to_string(),
+        secret: data["callback_secret"].as_str().map(|s| s.to_string()),
+    };
+    callback.validate()?;
+
+    Ok(Some(callback))
+}
+
+/// Push a finished job to its callback URL, if it has one
+fn notify_callback(app: &App, job_id: &str) {
+    let (callback, body) = {
+        let jobs = app.jobs.lock().unwrap();
+        let Some(job) = jobs.get(job_id).filter(|job| job.status.is_finished()) else {
             return;
-        }
+        };
+        let Some(callback) = job.callback.clone() else {
+            return;
+        };
 
-        // now safe to await
-        match whisper::whisper_transcribe(combined).await {
-            Ok(result) => {
-                let mut jobs = JOBS.lock().unwrap();
-                jobs.insert(
-                    job_id_clone,
-                    JobStatus::Completed(serde_json::to_string(&result).unwrap())
-                );
-            }
+        let payload = webhook::WebhookPayload { job_id, status: &job.status };
+        match serde_json::to_vec(&payload) {
+            Ok(body) => (callback, body),
             Err(e) => {
-                let mut jobs = JOBS.lock().unwrap();
-                jobs.insert(
-                    job_id_clone,
-                    JobStatus::Failed(format!("Transcription failed: {}", e))
-                );
+                eprintln!("Failed to serialize webhook for job {}: {}", job_id, e);
+                return;
             }
         }
+    };
+
+    webhook::deliver_webhook(job_id.to_string(), callback, body);
+}
+
+/// Queue a job on the worker pool, the whisper run never touches the async runtime
+///
+/// A job the pool refuses is left as it is, for the caller to fail or drop.
+fn spawn_job<T: Transcriber>(
+    app: &Arc<App>,
+    engine: &Arc<T>,
+    job_id: String,
+    session_id: String,
+    model: String,
+    options: TranscriptionOptions
+) -> Result<(), SubmitError> {
+    let worker_app = Arc::clone(app);
+    let engine = Arc::clone(engine);
+    let job_id_clone = job_id.clone();
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    set_stage(app, &job_id, JobStage::Queued);
+    app.queue.lock().unwrap().push_back(job_id.clone());
+    app.cancel_flags.lock().unwrap().insert(job_id.clone(), Arc::clone(&cancelled));
+
+    let submitted = app.workers.submit(move || {
+        let app = worker_app;
+        app.queue.lock().unwrap().retain(|id| *id != job_id);
+
+        let status = run_job(&app, &*engine, &job_id, &session_id, &model, &options, &cancelled);
+        update_job(&app, &job_id, status);
+        notify_callback(&app, &job_id);
+
+        // The job is settled, the journaled chunks are no longer needed
+        if let Err(e) = app.store.remove_session(&session_id) {
+            eprintln!("Failed to remove session {}: {}", session_id, e);
+        }
+        app.cancel_flags.lock().unwrap().remove(&job_id);
     });
 
-    Json(UploadResponse {
-        message: format!("Job started with ID: {}", job_id),
+    if submitted.is_err() {
+        app.queue.lock().unwrap().retain(|id| *id != job_id_clone);
+        app.cancel_flags.lock().unwrap().remove(&job_id_clone);
+    }
+    submitted
+}
+
+/// Take a job through every stage, returning how it ended
+fn run_job(
+    app: &Arc<App>,
+    engine: &impl Transcriber,
+    job_id: &str,
+    session_id: &str,
+    model: &str,
+    options: &TranscriptionOptions,
+    cancelled: &Arc<AtomicBool>
+) -> JobStatus {
+    let is_cancelled = || cancelled.load(Ordering::SeqCst);
+    if is_cancelled() {
+        return JobStatus::Cancelled;
+    }
+
+    set_stage(app, job_id, JobStage::Assembling);
+
+    let media = match app.store.assemble_session(session_id) {
+        Ok(Some(media)) => media,
+        Ok(None) => {
+            return JobStatus::Failed("No chunks found".to_string());
+        }
+        Err(e) => {
+            return JobStatus::Failed(format!("Failed to assemble upload: {}", e));
+        }
+    };
+
+    if std::fs::metadata(&media).map(|m| m.len()).unwrap_or(0) == 0 {
+        return JobStatus::Failed("No data after combining".to_string());
+    }
+
+    if let Err(e) = engine.load_model(model) {
+        return JobStatus::Failed(e.to_string());
+    }
+
+    let result = panic::catch_unwind(
+        AssertUnwindSafe(|| {
+            set_stage(app, job_id, JobStage::Decoding);
+            let pcm = engine.decode(&media)?;
+            if is_cancelled() {
+                return Err(anyhow::anyhow!("Cancelled"));
+            }
+
+            set_stage(app, job_id, JobStage::Transcribing);
+            let started = Instant::now();
+            let progress_app = Arc::clone(app);
+            let progress_job_id = job_id.to_string();
+            let run = whisper::TranscribeRun {
+                n_threads: app.config.threads_per_job,
+                parallel: app.config.parallel_windows,
+                on_progress: Arc::new(move |fraction: f32| {
+                    // Assume the rest goes as fast as what is done
+                    let eta_secs = (fraction > 0.0).then(|| {
+                        let elapsed = started.elapsed().as_secs_f32();
+                        ((elapsed * (1.0 - fraction)) / fraction).round() as u64
+                    });
+                    set_stage_fraction(&progress_app, &progress_job_id, fraction, eta_secs);
+                }),
+                on_segment: Arc::new({
+                    let app = Arc::clone(app);
+                    let job_id = job_id.to_string();
+                    move |segment| publish_segment(&app, &job_id, segment)
+                }),
+                cancelled: Arc::clone(cancelled),
+            };
+
+            let mut transcript = engine
+                .transcribe(model, &pcm, options, &run)
+                .map_err(|e| anyhow::anyhow!("Transcription failed: {}", e))?;
+
+            set_stage(app, job_id, JobStage::PostProcessing);
+            transcript.waveform = audio::waveform_peaks(&pcm, audio::WAVEFORM_PEAKS);
+            transcript.thumbnail = poster_frame(engine, &media);
+            Ok::<_, anyhow::Error>(transcript)
+        })
+    );
+
+    match result {
+        _ if is_cancelled() => JobStatus::Cancelled,
+        Ok(Ok(mut result)) => {
+            result.model = Some(model.to_string());
+            JobStatus::Completed(Box::new(result))
+        }
+        Ok(Err(e)) => JobStatus::Failed(e.to_string()),
+        Err(_) => JobStatus::Failed("Transcription panicked".to_string()),
+    }
+}
+
+/// Poster frame of a video upload, a job still completes without one
+fn poster_frame(engine: &impl Transcriber, media: &std::path::Path) -> Option<audio::Thumbnail> {
+    let thumbnail = engine
+        .probe(media)
+        .and_then(|info| Ok(audio::poster_frame(media, &info)?));
+
+    thumbnail.unwrap_or_else(|e| {
+        eprintln!("No thumbnail for {}: {}", media.display(), e);
+        None
     })
 }
 
-async fn check_job_status(Path(job_id): Path<String>) -> Json<JobStatus> {
-    let jobs: std::sync::MutexGuard<'_, HashMap<String, JobStatus>> = JOBS.lock().unwrap();
+/// Cancel a job that has not finished yet, or delete a finished one with its result
+async fn delete_job(
+    State(app): State<Arc<App>>,
+    Path(job_id): Path<String>
+) -> Result<Json<UploadResponse>, ApiError> {
+    let finished = {
+        let jobs = app.jobs.lock().unwrap();
+        match jobs.get(&job_id) {
+            Some(job) => job.status.is_finished(),
+            None => {
+                return Err(ApiError::NotFound(format!("Job {} not found", job_id)));
+            }
+        }
+    };
 
-    jobs.get(&job_id)
-        .cloned()
-        .map(Json)
-        .unwrap_or(Json(JobStatus::Failed("Job not found".to_string())))
-}
-
-async fn transcription_result(Path(job_id): Path<String>) -> Json<TranscriptionResponse> {
-    let jobs = JOBS.lock().unwrap();
-
-    match jobs.get(&job_id) {
-        Some(JobStatus::Completed(result_json)) => {
-            // Deserialize stored JSON back into TranscriptionResponse
-            let result: TranscriptionResponse = serde_json
-                ::from_str(result_json)
-                .unwrap_or(TranscriptionResponse {
-                    text: "Failed to parse result".to_string(),
-                    language: "unknown".to_string(),
-                    segments: vec![],
-                });
-            Json(result)
-        }
-        Some(JobStatus::Failed(err)) =>
-            Json(TranscriptionResponse {
-                text: format!("Error: {}", err),
-                language: "unknown".to_string(),
-                segments: vec![],
-            }),
-        _ =>
-            Json(TranscriptionResponse {
-                text: "Job is still pending.".to_string(),
-                language: "unknown".to_string(),
-                segments: vec![],
-            }),
+    if finished {
+        let deleted_job_id = job_id.clone();
+        tokio::task
+            ::spawn_blocking(move || forget_job(&app, &deleted_job_id)).await
+            .map_err(|e| ApiError::Internal(format!("Deleting job failed: {}", e)))?;
+
+        return Ok(Json(UploadResponse { message: format!("Job {} deleted", job_id) }));
     }
+
+    // Stops whisper at its next abort check, a queued job never starts
+    if let Some(flag) = app.cancel_flags.lock().unwrap().get(&job_id) {
+        flag.store(true, Ordering::SeqCst);
+    }
+    let cancelled_job_id = job_id.clone();
+    tokio::task
+        ::spawn_blocking(move || update_job(&app, &cancelled_job_id, JobStatus::Cancelled)).await
+        .map_err(|e| ApiError::Internal(format!("Cancelling job failed: {}", e)))?;
+
+    Ok(Json(UploadResponse { message: format!("Job {} cancelled", job_id) }))
+}
+
+async fn check_job_status(
+    State(app): State<Arc<App>>,
+    Path(job_id): Path<String>
+) -> Result<Json<JobStatusResponse>, ApiError> {
+    let (mut status, expires_at) = {
+        let jobs = app.jobs.lock().unwrap();
+        match jobs.get(&job_id) {
+            Some(job) => (job.status.clone(), job.expires_at(app.config.job_ttl_secs)),
+            None => {
+                return Err(ApiError::NotFound(format!("Job {} not found", job_id)));
+            }
+        }
+    };
+
+    set_queue_position(&app, &job_id, &mut status);
+
+    Ok(Json(JobStatusResponse { status: status.into(), expires_at }))
+}
+
+/// The queue moves on its own, so the position is worked out every time a status goes out
+fn set_queue_position(app: &App, job_id: &str, status: &mut JobStatus) {
+    if let JobStatus::InProgress(progress) = status {
+        if progress.stage == JobStage::Queued {
+            progress.queue_position = app.queue.lock()
+                .unwrap()
+                .iter()
+                .position(|id| *id == job_id)
+                .map(|i| (i as u64) + 1);
+        }
+    }
+}
+
+/// Follow a job as server-sent events instead of polling `/status/{job_id}`
+///
+/// `status` events carry the status as `/status` has it, and `segment` events each segment
+/// as whisper decodes it. A `segments` event holds every segment decoded so far, it comes
+/// after the first status when joining a running job, and again whenever the listener fell
+/// too far behind, replacing what it had. The stream ends with a `result` event holding the
+/// transcript, or an `error` event if the job failed or was cancelled.
+async fn job_events(
+    State(app): State<Arc<App>>,
+    Path(job_id): Path<String>
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    let (pending, receiver) = subscribe_job(&app, &job_id)?;
+
+    let events = stream::unfold(
+        (VecDeque::from(pending), receiver),
+        move |(mut pending, mut receiver)| {
+            let app = Arc::clone(&app);
+            let job_id = job_id.clone();
+            async move {
+                let event = match pending.pop_front() {
+                    Some(event) => event,
+                    None =>
+                        match receiver.as_mut()?.recv().await {
+                            Ok(event) => event,
+                            // Start over from where the job is, so no segment goes missing
+                            Err(RecvError::Lagged(_)) => {
+                                let (events, resubscribed) = subscribe_job(&app, &job_id).ok()?;
+                                pending = VecDeque::from(events);
+                                receiver = resubscribed;
+                                pending.pop_front()?
+                            }
+                            Err(RecvError::Closed) => {
+                                return None;
+                            }
+                        }
+                };
+
+                let finished = matches!(&event, JobEvent::Status(s) if s.is_finished());
+                let receiver = if finished { None } else { receiver };
+
+                Some((job_event(&app, &job_id, event), (pending, receiver)))
+            }
+        }
+    );
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// First events for a new listener of a job, and the channel of the ones after them
+///
+/// Subscribed under the jobs lock, so no update can slip in between the two.
+fn subscribe_job(
+    app: &App,
+    job_id: &str
+) -> Result<(Vec<JobEvent>, Option<broadcast::Receiver<JobEvent>>), ApiError> {
+    let jobs = app.jobs.lock().unwrap();
+    let status = match jobs.get(job_id) {
+        Some(job) => job.status.clone(),
+        None => {
+            return Err(ApiError::NotFound(format!("Job {} not found", job_id)));
+        }
+    };
+
+    if status.is_finished() {
+        return Ok((vec![JobEvent::Status(status)], None));
+    }
+
+    let mut channels = app.events.lock().unwrap();
+    let channel = channels
+        .entry(job_id.to_string())
+        .or_insert_with(|| JobChannel::new(JOB_EVENTS_CAPACITY));
+
+    let mut events = vec![JobEvent::Status(status)];
+    if !channel.segments.is_empty() {
+        events.push(JobEvent::Segments(channel.segments.clone()));
+    }
+
+    Ok((events, Some(channel.sender.subscribe())))
+}
+
+fn job_event(app: &App, job_id: &str, event: JobEvent) -> Result<Event, axum::Error> {
+    match event {
+        JobEvent::Segment(segment) => Event::default().event("segment").json_data(segment),
+        JobEvent::Segments(segments) => Event::default().event("segments").json_data(segments),
+        JobEvent::Status(JobStatus::Completed(result)) => {
+            Event::default().event("result").json_data(result)
+        }
+        JobEvent::Status(JobStatus::Failed(e)) => {
+            Event::default().event("error").json_data(ApiError::JobFailed(e).body())
+        }
+        JobEvent::Status(JobStatus::Cancelled) => {
+            let error = ApiError::JobFailed(format!("Job {} was cancelled", job_id));
+            Event::default().event("error").json_data(error.body())
+        }
+        JobEvent::Status(mut status) => {
+            set_queue_position(app, job_id, &mut status);
+            Event::default().event("status").json_data(status)
+        }
+    }
+}
+
+/// Transcript of a completed job, 202 with the current status while it is still running
+///
+/// JSON results can be fetched a page of segments at a time with `offset` and `limit`.
+async fn transcription_result(
+    State(app): State<Arc<App>>,
+    Path(job_id): Path<String>,
+    Query(query): Query<ExportQuery>
+) -> Response {
+    let status = match app.jobs.lock().unwrap().get(&job_id) {
+        Some(job) => job.status.clone(),
+        None => {
+            return ApiError::NotFound(format!("Job {} not found", job_id)).into_response();
+        }
+    };
+
+    let result = match status {
+        JobStatus::Completed(result) => result,
+        JobStatus::Failed(e) => {
+            return ApiError::JobFailed(e).into_response();
+        }
+        JobStatus::Cancelled => {
+            return ApiError::JobFailed(format!("Job {} was cancelled", job_id)).into_response();
+        }
+        pending => {
+            return (StatusCode::ACCEPTED, Json(pending)).into_response();
+        }
+    };
+
+    if query.format == ExportFormat::Json {
+        return match query.limit {
+            Some(0) => ApiError::BadRequest("limit must be at least 1".to_string()).into_response(),
+            Some(limit) => Json(TranscriptionPage::new(*result, query.offset, limit)).into_response(),
+            None => Json(result).into_response(),
+        };
+    }
+
+    export_result(&job_id, &result, query.format)
+}
+
+/// Completed transcript rendered as a file download
+fn export_result(job_id: &str, result: &TranscriptionResponse, format: ExportFormat) -> Response {
+    match render_transcript(result, format) {
+        Ok(body) =>
+            (
+                [
+                    (header::CONTENT_TYPE, format.content_type().to_string()),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"{}.{}\"", job_id, format.extension()),
+                    ),
+                ],
+                body,
+            ).into_response(),
+        Err(e) => ApiError::Internal(format!("Failed to render result: {}", e)).into_response(),
+    }
+}
+
+/// Models a job may ask for, listing reads the models directory so it runs off the async runtime
+async fn list_models<T: Transcriber>(
+    State(AppState { engine, .. }): State<AppState<T>>
+) -> Result<Json<ModelsResponse>, ApiError> {
+    tokio::task
+        ::spawn_blocking(move || ModelsResponse {
+            default_model: engine.default_model().to_string(),
+            models: engine.list_models(),
+        }).await
+        .map(Json)
+        .map_err(|e| ApiError::Internal(format!("Listing models failed: {}", e)))
 }
@@ -0,0 +1,13 @@
+use serde::{ Deserialize, Serialize };
+
+use super::MediaStream;
+
+/// What a media file contains, found without decoding it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MediaInfo {
+    /// Container format such as `mp4`, `matroska` or `wav`
+    pub container: Option<String>,
+    pub duration_ms: Option<u64>,
+    pub size: u64,
+    pub streams: Vec<MediaStream>,
+}
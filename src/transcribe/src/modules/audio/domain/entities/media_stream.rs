@@ -0,0 +1,16 @@
+use serde::{ Deserialize, Serialize };
+
+use super::StreamKind;
+
+/// A single track of a media file, fields the prober could not tell are left out
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MediaStream {
+    pub index: u32,
+    pub kind: StreamKind,
+    pub codec: Option<String>,
+    pub duration_ms: Option<u64>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
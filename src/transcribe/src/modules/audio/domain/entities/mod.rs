@@ -0,0 +1,11 @@
+pub mod media_info;
+pub mod media_stream;
+pub mod stream_kind;
+pub mod thumbnail;
+pub mod waveform;
+
+pub use media_info::*;
+pub use media_stream::*;
+pub use stream_kind::*;
+pub use thumbnail::*;
+pub use waveform::*;
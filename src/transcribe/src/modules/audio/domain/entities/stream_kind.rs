@@ -0,0 +1,9 @@
+use serde::{ Deserialize, Serialize };
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamKind {
+    Audio,
+    Video,
+    /// Subtitles, attachments, or a track the decoder could not identify
+    Other,
+}
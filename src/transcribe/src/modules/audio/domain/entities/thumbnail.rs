@@ -0,0 +1,9 @@
+use serde::{ Deserialize, Serialize };
+
+/// Still image of a video upload
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Thumbnail {
+    pub content_type: String,
+    /// Image bytes, base64 encoded
+    pub data: String,
+}
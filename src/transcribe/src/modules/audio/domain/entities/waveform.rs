@@ -0,0 +1,9 @@
+use serde::{ Deserialize, Serialize };
+
+/// Loudness outline of a recording, small enough to ship with the transcript
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Waveform {
+    /// Highest level of each equal slice of the audio, 255 being the loudest slice
+    pub peaks: Vec<u8>,
+    pub duration_ms: u64,
+}
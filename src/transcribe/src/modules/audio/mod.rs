@@ -0,0 +1,5 @@
+pub mod domain;
+pub mod service;
+
+pub use domain::*;
+pub use service::*;
@@ -0,0 +1,114 @@
+use std::fs::File;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{ anyhow, Result };
+use symphonia::core::{
+    audio::SampleBuffer,
+    codecs::{ DecoderOptions, CODEC_TYPE_NULL },
+    errors::Error as SymphoniaError,
+    formats::FormatOptions,
+    io::MediaSourceStream,
+    meta::MetadataOptions,
+    probe::Hint,
+};
+
+use super::{ downmix, extract_audio_from_video, read_wav, Resampler, WHISPER_SAMPLE_RATE };
+
+/// Decode any supported media file into mono 16kHz samples for whisper
+///
+/// Decoding happens in-process with symphonia, reading the file and resampling it a packet at
+/// a time, so only the 16kHz result is held in memory. ffmpeg is only tried when symphonia
+/// cannot handle the file and `ffmpeg_fallback` is enabled.
+pub fn decode_media(path: &Path, ffmpeg_fallback: bool) -> Result<Vec<f32>> {
+    let native_error = match decode_with_symphonia(path) {
+        Ok(pcm) => {
+            return Ok(pcm);
+        }
+        Err(e) => e,
+    };
+
+    if !ffmpeg_fallback {
+        return Err(anyhow!("Audio decoding failed: {}", native_error));
+    }
+
+    extract_audio_from_video(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|wav| read_wav(&wav))
+        .map_err(|e| anyhow!("Audio decoding failed: {} (ffmpeg fallback: {})", native_error, e))
+}
+
+fn decode_with_symphonia(path: &Path) -> Result<Vec<f32>> {
+    let file = File::open(path).map_err(|e| anyhow!("Failed to open media: {}", e))?;
+    let source = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let probed = symphonia::default
+        ::get_probe()
+        .format(&Hint::new(), source, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| anyhow!("Unsupported media format: {}", e))?;
+    let mut format = probed.format;
+
+    // Pick the first audio track, video files usually carry exactly one
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL && t.codec_params.sample_rate.is_some())
+        .ok_or_else(|| anyhow!("No audio track found"))?;
+    let track_id = track.id;
+    let sample_rate = track.codec_params.sample_rate.unwrap_or(WHISPER_SAMPLE_RATE);
+
+    let mut decoder = symphonia::default
+        ::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| anyhow!("Unsupported audio codec: {}", e))?;
+
+    let mut resampler = Resampler::new(sample_rate, WHISPER_SAMPLE_RATE);
+    let mut pcm = Vec::new();
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == ErrorKind::UnexpectedEof => {
+                break;
+            }
+            Err(e) => {
+                return Err(anyhow!("Failed to read media: {}", e));
+            }
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A corrupt packet only loses a few milliseconds, keep going
+            Err(SymphoniaError::DecodeError(e)) => {
+                eprintln!("Skipping undecodable packet: {}", e);
+                continue;
+            }
+            Err(e) => {
+                return Err(anyhow!("Failed to decode audio: {}", e));
+            }
+        };
+
+        let spec = *decoded.spec();
+        let channels = spec.channels.count().max(1);
+
+        let buf = match &mut buffer {
+            Some(buf) if buf.capacity() >= decoded.capacity() * channels => buf,
+            _ => buffer.insert(SampleBuffer::new(decoded.capacity() as u64, spec)),
+        };
+        buf.copy_interleaved_ref(decoded);
+
+        resampler.push(&downmix(buf.samples(), channels), &mut pcm);
+    }
+    resampler.finish(&mut pcm);
+
+    if pcm.is_empty() {
+        return Err(anyhow!("Audio track contains no samples"));
+    }
+
+    Ok(pcm)
+}
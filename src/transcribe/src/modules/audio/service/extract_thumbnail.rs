@@ -0,0 +1,80 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::{ Command, Stdio };
+
+use base64::{ engine::general_purpose::STANDARD, Engine };
+
+use crate::modules::audio::domain::{ MediaInfo, StreamKind, Thumbnail };
+
+/// Width of the poster frame, the height follows the aspect ratio of the video
+const THUMBNAIL_WIDTH: u32 = 320;
+
+/// Latest point a poster frame is taken from
+const MAX_POSTER_MS: u64 = 10_000;
+
+/// Grab a poster frame of a video with the ffmpeg binary, `None` for audio-only media
+///
+/// The frame comes from a tenth of the way in, at most `MAX_POSTER_MS`, since videos
+/// often open on a black frame.
+pub fn poster_frame(media: &Path, info: &MediaInfo) -> io::Result<Option<Thumbnail>> {
+    if !info.streams.iter().any(|s| s.kind == StreamKind::Video) {
+        return Ok(None);
+    }
+
+    let at_ms = info.duration_ms.map(|d| (d / 10).min(MAX_POSTER_MS)).unwrap_or(0);
+    let jpeg = extract_frame(media, at_ms)?;
+
+    Ok(
+        Some(Thumbnail {
+            content_type: "image/jpeg".to_string(),
+            data: STANDARD.encode(jpeg),
+        })
+    )
+}
+
+fn extract_frame(media: &Path, at_ms: u64) -> io::Result<Vec<u8>> {
+    let temp_image = tempfile::Builder::new().suffix(".jpg").tempfile()?;
+
+    let output = Command::new("ffmpeg")
+        .args(["-nostdin", "-y", "-ss", &format!("{}.{:03}", at_ms / 1000, at_ms % 1000), "-i"])
+        .arg(media)
+        .args([
+            "-frames:v",
+            "1",
+            "-vf",
+            &format!("scale={}:-2", THUMBNAIL_WIDTH),
+            "-q:v",
+            "5",
+            "-f",
+            "image2",
+        ])
+        .arg(temp_image.path())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| {
+            if e.kind() == io::ErrorKind::NotFound {
+                io::Error::new(e.kind(), "ffmpeg is not installed")
+            } else {
+                e
+            }
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("no output");
+        return Err(io::Error::other(format!("ffmpeg exited with {}: {}", output.status, reason)));
+    }
+
+    let jpeg = fs::read(temp_image.path())?;
+    if jpeg.is_empty() {
+        return Err(io::Error::other("ffmpeg produced no frame"));
+    }
+
+    Ok(jpeg)
+}
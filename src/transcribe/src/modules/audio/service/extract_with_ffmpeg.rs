@@ -0,0 +1,51 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::{ Command, Stdio };
+
+/// Extract mono 16kHz WAV audio with the ffmpeg binary, used as a fallback decoder
+///
+/// ffmpeg writes to a temp file rather than a pipe, so the WAV header carries real sizes.
+pub fn extract_audio_from_video(video_path: &Path) -> io::Result<Vec<u8>> {
+    let temp_audio = tempfile::Builder::new().suffix(".wav").tempfile()?;
+
+    // ffmpeg command: extract mono 16kHz WAV
+    let output = Command::new("ffmpeg")
+        .args([
+            "-nostdin",
+            "-y",
+            "-i",
+            video_path.to_str().unwrap(),
+            "-vn",
+            "-ac",
+            "1",
+            "-ar",
+            "16000",
+            "-f",
+            "wav",
+            temp_audio.path().to_str().unwrap(),
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| {
+            if e.kind() == io::ErrorKind::NotFound {
+                io::Error::new(e.kind(), "ffmpeg is not installed")
+            } else {
+                e
+            }
+        })?;
+
+    if !output.status.success() {
+        // The last line of ffmpeg's log is usually the actual reason
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("no output");
+        return Err(io::Error::other(format!("ffmpeg exited with {}: {}", output.status, reason)));
+    }
+
+    fs::read(temp_audio.path())
+}
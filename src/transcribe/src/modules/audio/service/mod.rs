@@ -0,0 +1,17 @@
+pub mod decode_media;
+pub mod extract_thumbnail;
+pub mod extract_with_ffmpeg;
+pub mod pcm;
+pub mod probe_media;
+pub mod resample;
+pub mod vad;
+pub mod waveform_peaks;
+
+pub use decode_media::*;
+pub use extract_thumbnail::*;
+pub use extract_with_ffmpeg::*;
+pub use pcm::*;
+pub use probe_media::*;
+pub use resample::*;
+pub use vad::*;
+pub use waveform_peaks::*;
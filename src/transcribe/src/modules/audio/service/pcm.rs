@@ -0,0 +1,122 @@
+use std::io::Cursor;
+
+use anyhow::{ anyhow, Result };
+use hound::{ SampleFormat, WavReader };
+
+use super::{ resample, WHISPER_SAMPLE_RATE };
+
+const MIN_SAMPLE_RATE: u32 = 8_000;
+const MAX_SAMPLE_RATE: u32 = 192_000;
+const MAX_CHANNELS: u16 = 8;
+
+/// Read a WAV file into mono 16kHz samples
+///
+/// Accepts 8/16/24/32-bit integer and 32-bit float PCM with any channel layout up to
+/// `MAX_CHANNELS`, everything else is rejected instead of being fed to whisper as noise.
+pub fn read_wav(data: &[u8]) -> Result<Vec<f32>> {
+    let reader = WavReader::new(Cursor::new(data)).map_err(|e| anyhow!("Malformed WAV: {}", e))?;
+    let spec = reader.spec();
+
+    if spec.channels == 0 || spec.channels > MAX_CHANNELS {
+        return Err(anyhow!("Unsupported WAV channel count: {}", spec.channels));
+    }
+    if !(MIN_SAMPLE_RATE..=MAX_SAMPLE_RATE).contains(&spec.sample_rate) {
+        return Err(anyhow!("Unsupported WAV sample rate: {} Hz", spec.sample_rate));
+    }
+
+    let interleaved: Vec<f32> = match (spec.sample_format, spec.bits_per_sample) {
+        (SampleFormat::Float, 32) =>
+            reader
+                .into_samples::<f32>()
+                .collect::<Result<_, _>>()
+                .map_err(|e| anyhow!("Malformed WAV data: {}", e))?,
+        (SampleFormat::Int, bits @ (8 | 16 | 24 | 32)) => {
+            // Scale to [-1.0, 1.0) based on the declared bit depth
+            let scale = (1u64 << (bits - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|s| s.map(|s| (s as f32) / scale))
+                .collect::<Result<_, _>>()
+                .map_err(|e| anyhow!("Malformed WAV data: {}", e))?
+        }
+        (format, bits) => {
+            return Err(anyhow!("Unsupported WAV sample format: {:?} {}-bit", format, bits));
+        }
+    };
+
+    if interleaved.is_empty() {
+        return Err(anyhow!("WAV file contains no samples"));
+    }
+
+    let mono = downmix(&interleaved, spec.channels as usize);
+    Ok(resample(&mono, spec.sample_rate, WHISPER_SAMPLE_RATE))
+}
+
+/// Average interleaved frames into a single channel
+pub fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+
+    interleaved
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / (channels as f32))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Integer PCM WAV of `frames` silent frames, with a plain 16-byte `fmt ` chunk
+    fn wav(channels: u16, sample_rate: u32, bits: u16, frames: u32) -> Vec<u8> {
+        let block_align = channels * (bits / 8);
+        let data_len = frames * (block_align as u32);
+
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&channels.to_le_bytes());
+        wav.extend_from_slice(&sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(sample_rate * (block_align as u32)).to_le_bytes());
+        wav.extend_from_slice(&block_align.to_le_bytes());
+        wav.extend_from_slice(&bits.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        wav.resize(wav.len() + (data_len as usize), 0);
+        wav
+    }
+
+    fn rejection(data: &[u8]) -> String {
+        read_wav(data).expect_err("WAV should be rejected").to_string()
+    }
+
+    #[test]
+    fn reads_supported_wav() {
+        let pcm = read_wav(&wav(2, 8_000, 16, 800)).unwrap();
+
+        assert_eq!(pcm.len(), 1_600);
+        assert!(pcm.iter().all(|s| *s == 0.0));
+    }
+
+    #[test]
+    fn rejects_wrong_sample_rate() {
+        assert_eq!(rejection(&wav(1, 4_000, 16, 400)), "Unsupported WAV sample rate: 4000 Hz");
+        assert_eq!(rejection(&wav(1, 384_000, 16, 400)), "Unsupported WAV sample rate: 384000 Hz");
+    }
+
+    #[test]
+    fn rejects_wrong_channel_count() {
+        assert_eq!(rejection(&wav(9, 16_000, 16, 400)), "Unsupported WAV channel count: 9");
+    }
+
+    #[test]
+    fn rejects_wrong_bit_depth() {
+        let error = rejection(&wav(1, 16_000, 40, 400));
+
+        assert_eq!(error, "Unsupported WAV sample format: Int 40-bit");
+    }
+}
@@ -0,0 +1,207 @@
+use std::fs::{ self, File };
+use std::io::{ self, Read };
+use std::path::Path;
+use std::process::{ Command, Stdio };
+
+use anyhow::{ anyhow, Result };
+use symphonia::core::{
+    codecs::CODEC_TYPE_NULL,
+    formats::FormatOptions,
+    io::MediaSourceStream,
+    meta::MetadataOptions,
+    probe::Hint,
+};
+
+use crate::modules::audio::domain::{ MediaInfo, MediaStream, StreamKind };
+
+/// Containers that may carry video, symphonia only sees their audio tracks
+const VIDEO_CONTAINERS: &[&str] = &["mp4", "mov", "matroska", "avi"];
+
+/// Find the container, duration and streams of a media file without decoding it
+///
+/// symphonia reads the headers in-process. When it cannot open the file, or the container
+/// may hold tracks it does not understand such as video, `ffprobe` is asked instead if
+/// `ffmpeg_fallback` is enabled. What symphonia found is kept when ffprobe is missing.
+pub fn probe_media(path: &Path, ffmpeg_fallback: bool) -> Result<MediaInfo> {
+    let size = fs::metadata(path).map_err(|e| anyhow!("Failed to open media: {}", e))?.len();
+    let container = sniff_container(path);
+
+    let native = probe_with_symphonia(path);
+    let complete = match &native {
+        Ok(streams) => {
+            let maybe_video = container.is_some_and(|c| VIDEO_CONTAINERS.contains(&c));
+            !maybe_video && streams.iter().all(|s| s.kind != StreamKind::Other)
+        }
+        Err(_) => false,
+    };
+
+    let (format_name, duration_ms, streams) = match native {
+        Ok(streams) if complete || !ffmpeg_fallback => (None, None, streams),
+        Err(e) if !ffmpeg_fallback => {
+            return Err(anyhow!("Unsupported media: {}", e));
+        }
+        native =>
+            match (probe_with_ffprobe(path), native) {
+                (Ok(probed), _) => probed,
+                (Err(_), Ok(streams)) => (None, None, streams),
+                (Err(e), Err(native_error)) => {
+                    return Err(
+                        anyhow!("Unsupported media: {} (ffprobe fallback: {})", native_error, e)
+                    );
+                }
+            }
+    };
+
+    // Without a duration for the whole file, the longest stream is as close as it gets
+    let duration_ms = duration_ms.or_else(|| {
+        streams
+            .iter()
+            .filter_map(|s| s.duration_ms)
+            .max()
+    });
+
+    Ok(MediaInfo {
+        container: container.map(|c| c.to_string()).or(format_name),
+        duration_ms,
+        size,
+        streams,
+    })
+}
+
+fn probe_with_symphonia(path: &Path) -> Result<Vec<MediaStream>> {
+    let file = File::open(path).map_err(|e| anyhow!("Failed to open media: {}", e))?;
+    let source = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let probed = symphonia::default
+        ::get_probe()
+        .format(&Hint::new(), source, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| anyhow!("Unsupported media format: {}", e))?;
+    let codecs = symphonia::default::get_codecs();
+
+    let streams = probed.format
+        .tracks()
+        .iter()
+        .enumerate()
+        .map(|(i, track)| {
+            let params = &track.codec_params;
+            let is_audio = params.codec != CODEC_TYPE_NULL && params.sample_rate.is_some();
+
+            let duration_ms = match (params.n_frames, params.time_base, params.sample_rate) {
+                (Some(frames), Some(time_base), _) => {
+                    let time = time_base.calc_time(frames);
+                    Some(time.seconds * 1000 + (time.frac * 1000.0).round() as u64)
+                }
+                (Some(frames), None, Some(rate)) if rate > 0 => {
+                    Some((frames * 1000) / (rate as u64))
+                }
+                _ => None,
+            };
+
+            MediaStream {
+                index: i as u32,
+                kind: if is_audio { StreamKind::Audio } else { StreamKind::Other },
+                codec: codecs.get_codec(params.codec).map(|c| c.short_name.to_string()),
+                duration_ms,
+                sample_rate: params.sample_rate,
+                channels: params.channels.map(|c| c.count() as u32),
+                width: None,
+                height: None,
+            }
+        })
+        .collect();
+
+    Ok(streams)
+}
+
+/// Ask the ffprobe binary, returning the format name, the duration and the streams
+fn probe_with_ffprobe(path: &Path) -> Result<(Option<String>, Option<u64>, Vec<MediaStream>)> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "error", "-print_format", "json", "-show_format", "-show_streams"])
+        .arg(path)
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| {
+            if e.kind() == io::ErrorKind::NotFound {
+                anyhow!("ffprobe is not installed")
+            } else {
+                anyhow!("Failed to run ffprobe: {}", e)
+            }
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("no output");
+        return Err(anyhow!("ffprobe exited with {}: {}", output.status, reason));
+    }
+
+    let report: serde_json::Value = serde_json
+        ::from_slice(&output.stdout)
+        .map_err(|e| anyhow!("Invalid ffprobe output: {}", e))?;
+
+    // Numbers such as durations and sample rates come as strings
+    let number = |value: &serde_json::Value| {
+        value
+            .as_str()
+            .and_then(|s| s.parse::<f64>().ok())
+            .or_else(|| value.as_f64())
+    };
+    let millis = |value: &serde_json::Value| number(value).map(|secs| (secs * 1000.0).round() as u64);
+    let small = |value: &serde_json::Value| number(value).map(|n| n as u32);
+
+    let streams = report["streams"]
+        .as_array()
+        .map(|streams| streams.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .map(|(i, stream)| MediaStream {
+            index: stream["index"].as_u64().unwrap_or(i as u64) as u32,
+            kind: match stream["codec_type"].as_str() {
+                Some("audio") => StreamKind::Audio,
+                Some("video") => StreamKind::Video,
+                _ => StreamKind::Other,
+            },
+            codec: stream["codec_name"].as_str().map(|c| c.to_string()),
+            duration_ms: millis(&stream["duration"]),
+            sample_rate: small(&stream["sample_rate"]),
+            channels: small(&stream["channels"]),
+            width: small(&stream["width"]),
+            height: small(&stream["height"]),
+        })
+        .collect();
+
+    // ffprobe lists every name of a format, such as "mov,mp4,m4a,3gp,3g2,mj2"
+    let format_name = report["format"]["format_name"]
+        .as_str()
+        .and_then(|names| names.split(',').next())
+        .map(|name| name.to_string());
+
+    Ok((format_name, millis(&report["format"]["duration"]), streams))
+}
+
+/// Name the container from the first bytes of the file
+fn sniff_container(path: &Path) -> Option<&'static str> {
+    let mut header = [0u8; 12];
+    File::open(path).and_then(|mut file| file.read_exact(&mut header)).ok()?;
+
+    match &header {
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E'] => Some("wav"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'A', b'V', b'I', b' '] => Some("avi"),
+        [b'F', b'O', b'R', b'M', _, _, _, _, b'A', b'I', b'F', _] => Some("aiff"),
+        [_, _, _, _, b'f', b't', b'y', b'p', b'q', b't', b' ', b' '] => Some("mov"),
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => Some("mp4"),
+        [0x1a, 0x45, 0xdf, 0xa3, ..] => Some("matroska"),
+        [b'O', b'g', b'g', b'S', ..] => Some("ogg"),
+        [b'f', b'L', b'a', b'C', ..] => Some("flac"),
+        [b'c', b'a', b'f', b'f', ..] => Some("caf"),
+        [0xff, second, ..] if second & 0xf6 == 0xf0 => Some("aac"),
+        [b'I', b'D', b'3', ..] => Some("mp3"),
+        [0xff, second, ..] if second & 0xe0 == 0xe0 => Some("mp3"),
+        _ => None,
+    }
+}
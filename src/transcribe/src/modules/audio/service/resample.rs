@@ -0,0 +1,173 @@
+use std::f64::consts::PI;
+
+/// Sample rate whisper expects its input in
+pub const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Width of the sinc kernel on each side, in zero crossings
+const ZERO_CROSSINGS: f64 = 8.0;
+
+/// Resample mono audio with a Hann-windowed sinc filter
+///
+/// The filter cutoff follows the lower of the two rates, so downsampling does not alias.
+pub fn resample(input: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    let mut resampler = Resampler::new(from_rate, to_rate);
+    let mut output = Vec::with_capacity(resampler.output_len(input.len() as u64) as usize);
+    resampler.push(input, &mut output);
+    resampler.finish(&mut output);
+    output
+}
+
+/// Resampler fed a piece of audio at a time, such as one decoded packet
+///
+/// Only the input the next output samples still reach back to is kept. The two rates are
+/// reduced to `to / from = phases / step`, so every output sample falls on one of `phases`
+/// fractional positions between two input samples, and the windowed-sinc kernel of each of
+/// them is computed once up front.
+pub struct Resampler {
+    /// Input samples between two output samples, in units of `1 / phases` of an input sample
+    step: u64,
+    phases: u64,
+    half_width: u64,
+    /// `2 * half_width` weights per phase, for input `base - half_width + 1 ..= base + half_width`
+    kernels: Vec<f64>,
+    /// Input not yet behind every output sample still to come, starting at `history_start`
+    history: Vec<f32>,
+    history_start: u64,
+    received: u64,
+    produced: u64,
+}
+
+impl Resampler {
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        let divisor = gcd(from_rate.max(1) as u64, to_rate.max(1) as u64);
+        let step = (from_rate.max(1) as u64) / divisor;
+        let phases = (to_rate.max(1) as u64) / divisor;
+
+        if step == phases {
+            return Resampler {
+                step: 1,
+                phases: 1,
+                half_width: 0,
+                kernels: vec![],
+                history: vec![],
+                history_start: 0,
+                received: 0,
+                produced: 0,
+            };
+        }
+
+        let cutoff = ((phases as f64) / (step as f64)).min(1.0);
+        let half_width = (ZERO_CROSSINGS / cutoff).ceil() as u64;
+        let taps = 2 * half_width;
+
+        let mut kernels = Vec::with_capacity((phases * taps) as usize);
+        for phase in 0..phases {
+            let fraction = (phase as f64) / (phases as f64);
+            for tap in 0..taps {
+                // Distance from the output position to the input sample this tap weighs
+                let x = (fraction + (half_width as f64) - 1.0 - (tap as f64)) * cutoff;
+                kernels.push(sinc(x) * hann(x / ZERO_CROSSINGS));
+            }
+        }
+
+        Resampler {
+            step,
+            phases,
+            half_width,
+            kernels,
+            history: vec![],
+            history_start: 0,
+            received: 0,
+            produced: 0,
+        }
+    }
+
+    /// Output samples `input_len` input samples resample to
+    pub fn output_len(&self, input_len: u64) -> u64 {
+        (input_len * self.phases) / self.step
+    }
+
+    /// Add input, appending every output sample it completes to `output`
+    pub fn push(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        if self.kernels.is_empty() {
+            output.extend_from_slice(input);
+            return;
+        }
+
+        self.history.extend_from_slice(input);
+        self.received += input.len() as u64;
+
+        // An output sample is complete once its last tap has arrived
+        while self.position(self.produced).0 + self.half_width < self.received {
+            output.push(self.output_sample(self.produced));
+            self.produced += 1;
+        }
+
+        let keep_from = self.position(self.produced).0.saturating_sub(self.half_width - 1);
+        if keep_from > self.history_start {
+            let drop = ((keep_from - self.history_start) as usize).min(self.history.len());
+            self.history.drain(..drop);
+            self.history_start += drop as u64;
+        }
+    }
+
+    /// Produce the output samples left, whose kernels reach past the end of the input
+    pub fn finish(mut self, output: &mut Vec<f32>) {
+        let total = self.output_len(self.received);
+        while self.produced < total {
+            output.push(self.output_sample(self.produced));
+            self.produced += 1;
+        }
+    }
+
+    /// Input sample an output sample is at or just after, and the phase between it and the next
+    fn position(&self, index: u64) -> (u64, u64) {
+        let at = index * self.step;
+        (at / self.phases, at % self.phases)
+    }
+
+    fn output_sample(&self, index: u64) -> f32 {
+        let (base, phase) = self.position(index);
+        let taps = (2 * self.half_width) as usize;
+        let kernel = &self.kernels[(phase as usize) * taps..(phase as usize + 1) * taps];
+
+        // Taps before the start or past the end of the input are left out, and the rest
+        // weighted up to make up for them
+        let first = (base + 1).saturating_sub(self.half_width);
+        let last = (base + self.half_width).min(self.received.saturating_sub(1));
+
+        let mut acc = 0.0;
+        let mut weight_sum = 0.0;
+        for j in first..=last {
+            let weight = kernel[(j + self.half_width - 1 - base) as usize];
+            acc += (self.history[(j - self.history_start) as usize] as f64) * weight;
+            weight_sum += weight;
+        }
+
+        if weight_sum > 0.0 {
+            (acc / weight_sum) as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+fn hann(x: f64) -> f64 {
+    if x.abs() >= 1.0 {
+        0.0
+    } else {
+        0.5 * (1.0 + (PI * x).cos())
+    }
+}
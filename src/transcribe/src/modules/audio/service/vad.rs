@@ -0,0 +1,211 @@
+use std::ops::Range;
+
+use super::WHISPER_SAMPLE_RATE;
+
+const FRAME_MS: usize = 30;
+const FRAME_SIZE: usize = ((WHISPER_SAMPLE_RATE as usize) * FRAME_MS) / 1000;
+/// Speech has to be this many times louder than the noise floor, about 10 dB
+const SPEECH_OVER_NOISE: f32 = 3.0;
+/// The threshold never drops below this, so digital silence is never taken for speech
+const MIN_SPEECH_RMS: f32 = 0.003;
+/// Pauses shorter than this stay inside the surrounding speech
+const MIN_SILENCE_MS: usize = 600;
+/// Bursts shorter than this are clicks or breaths
+const MIN_SPEECH_MS: usize = 150;
+/// Audio kept on both sides of the speech so word edges are not clipped
+const PADDING_MS: usize = 200;
+/// Speech regions separated by a longer pause go to different windows
+const MAX_MERGED_GAP_MS: usize = 3000;
+
+/// Find the speech in mono 16kHz samples, as sample ranges
+///
+/// Frames are compared to a threshold derived from the recording's own noise floor, so
+/// quiet recordings and noisy rooms both work without tuning.
+pub fn detect_speech(pcm: &[f32]) -> Vec<Range<usize>> {
+    let energies: Vec<f32> = pcm.chunks(FRAME_SIZE).map(rms).collect();
+    if energies.is_empty() {
+        return vec![];
+    }
+
+    let mut sorted = energies.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let noise_floor = sorted[sorted.len() / 10];
+    let median = sorted[sorted.len() / 2];
+    // Recordings without pauses have no real noise floor, never cut into typical speech there
+    let threshold = (noise_floor * SPEECH_OVER_NOISE).min(median * 0.5).max(MIN_SPEECH_RMS);
+
+    // Runs of voiced frames, short pauses bridged
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    for (frame, energy) in energies.iter().enumerate() {
+        if *energy < threshold {
+            continue;
+        }
+        match runs.last_mut() {
+            Some(run) if frame - run.end < MIN_SILENCE_MS / FRAME_MS => {
+                run.end = frame + 1;
+            }
+            _ => runs.push(frame..frame + 1),
+        }
+    }
+
+    let padding = (PADDING_MS / FRAME_MS) * FRAME_SIZE;
+    let mut regions: Vec<Range<usize>> = Vec::new();
+    for run in runs {
+        if run.len() * FRAME_MS < MIN_SPEECH_MS {
+            continue;
+        }
+
+        let start = (run.start * FRAME_SIZE).saturating_sub(padding);
+        let end = (run.end * FRAME_SIZE + padding).min(pcm.len());
+        match regions.last_mut() {
+            Some(region) if start <= region.end => {
+                region.end = end;
+            }
+            _ => regions.push(start..end),
+        }
+    }
+
+    regions
+}
+
+/// Group speech regions into windows of at most `max_window` samples
+///
+/// Regions close to each other share a window so whisper keeps some context, long pauses are
+/// left out entirely. A region longer than a window is cut at its quietest point near the limit.
+pub fn plan_windows(pcm: &[f32], speech: &[Range<usize>], max_window: usize) -> Vec<Range<usize>> {
+    let max_window = max_window.max(FRAME_SIZE * 2);
+    let max_gap = ms_to_samples(MAX_MERGED_GAP_MS);
+
+    let mut windows: Vec<Range<usize>> = Vec::new();
+    let mut current: Option<Range<usize>> = None;
+
+    for region in speech {
+        let mut region = region.clone();
+
+        while region.len() > max_window {
+            if let Some(window) = current.take() {
+                windows.push(window);
+            }
+            let search = region.start + (max_window * 3) / 4..region.start + max_window;
+            let cut = quietest_point(pcm, search);
+            windows.push(region.start..cut);
+            region.start = cut;
+        }
+
+        match &mut current {
+            Some(window) if
+                region.start - window.end <= max_gap &&
+                region.end - window.start <= max_window
+            => {
+                window.end = region.end;
+            }
+            _ => {
+                if let Some(window) = current.replace(region) {
+                    windows.push(window);
+                }
+            }
+        }
+    }
+
+    if let Some(window) = current {
+        windows.push(window);
+    }
+
+    windows
+}
+
+/// Windows to transcribe, the speech found by `detect_speech` or the whole recording without VAD
+///
+/// Bounded windows keep memory flat and timestamps from drifting on long recordings.
+pub fn speech_windows(pcm: &[f32], vad: bool, max_window_secs: u64) -> Vec<Range<usize>> {
+    let max_window = (max_window_secs as usize) * (WHISPER_SAMPLE_RATE as usize);
+    let speech = if vad { detect_speech(pcm) } else { std::iter::once(0..pcm.len()).collect() };
+    plan_windows(pcm, &speech, max_window)
+}
+
+/// Middle of the quietest frame in a range of samples
+fn quietest_point(pcm: &[f32], range: Range<usize>) -> usize {
+    let start = range.start;
+    let end = range.end.min(pcm.len());
+
+    pcm[start..end]
+        .chunks(FRAME_SIZE)
+        .enumerate()
+        .min_by(|(_, a), (_, b)| rms(a).total_cmp(&rms(b)))
+        .map(|(i, frame)| start + i * FRAME_SIZE + frame.len() / 2)
+        .unwrap_or(end)
+}
+
+fn rms(frame: &[f32]) -> f32 {
+    (
+        frame
+            .iter()
+            .map(|s| s * s)
+            .sum::<f32>() / (frame.len().max(1) as f32)
+    ).sqrt()
+}
+
+fn ms_to_samples(ms: usize) -> usize {
+    (ms * (WHISPER_SAMPLE_RATE as usize)) / 1000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: usize = WHISPER_SAMPLE_RATE as usize;
+
+    /// Loud noise everywhere, except digital silence over `quiet`
+    fn noise(len: usize, quiet: Range<usize>) -> Vec<f32> {
+        (0..len)
+            .map(|i| if quiet.contains(&i) { 0.0 } else if i % 2 == 0 { 0.5 } else { -0.5 })
+            .collect()
+    }
+
+    #[test]
+    fn silence_has_no_windows() {
+        let pcm = vec![0.0; 10 * SECOND];
+        let speech = detect_speech(&pcm);
+
+        assert_eq!(speech, vec![]);
+        assert_eq!(plan_windows(&pcm, &speech, 30 * SECOND), vec![]);
+    }
+
+    #[test]
+    fn short_input_is_one_window() {
+        let pcm = noise(SECOND / 2, 0..0);
+
+        assert_eq!(speech_windows(&pcm, false, 30), vec![0..pcm.len()]);
+    }
+
+    #[test]
+    fn long_region_is_cut_at_the_quietest_point() {
+        // One silent frame of the search, which starts three quarters into the 10s window
+        let quiet = (15 * SECOND) / 2 + 20 * FRAME_SIZE;
+        let pcm = noise(25 * SECOND, quiet..quiet + FRAME_SIZE);
+        let windows = speech_windows(&pcm, false, 10);
+
+        let cut = quiet + FRAME_SIZE / 2;
+        assert_eq!(windows[0], 0..cut);
+        assert_eq!(windows[1].start, cut);
+        assert_contiguous(&windows, pcm.len(), 10 * SECOND);
+    }
+
+    #[test]
+    fn long_region_without_a_pause_is_still_cut() {
+        let pcm = noise(25 * SECOND, 0..0);
+        let windows = speech_windows(&pcm, false, 10);
+
+        assert_eq!(windows.len(), 3);
+        assert_contiguous(&windows, pcm.len(), 10 * SECOND);
+    }
+
+    fn assert_contiguous(windows: &[Range<usize>], len: usize, max_window: usize) {
+        assert_eq!(windows.first().map(|w| w.start), Some(0));
+        assert_eq!(windows.last().map(|w| w.end), Some(len));
+        for pair in windows.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        assert!(windows.iter().all(|w| !w.is_empty() && w.len() <= max_window));
+    }
+}
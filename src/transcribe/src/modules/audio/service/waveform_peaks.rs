@@ -0,0 +1,32 @@
+use super::WHISPER_SAMPLE_RATE;
+use crate::modules::audio::domain::Waveform;
+
+/// Peaks in a waveform, enough to draw it across a wide screen
+pub const WAVEFORM_PEAKS: usize = 1000;
+
+/// Reduce mono 16kHz samples to `count` peaks, scaled so the loudest one is 255
+///
+/// Returns `None` when there is no audio to outline.
+pub fn waveform_peaks(pcm: &[f32], count: usize) -> Option<Waveform> {
+    if pcm.is_empty() || count == 0 {
+        return None;
+    }
+
+    let slice_len = pcm.len().div_ceil(count);
+    let peaks: Vec<f32> = pcm
+        .chunks(slice_len)
+        .map(|slice| slice.iter().fold(0.0f32, |peak, s| peak.max(s.abs())))
+        .collect();
+
+    // Quiet recordings still get a readable outline
+    let loudest = peaks.iter().cloned().fold(0.0f32, f32::max);
+    let scale = if loudest > 0.0 { 255.0 / loudest } else { 0.0 };
+
+    Some(Waveform {
+        peaks: peaks
+            .iter()
+            .map(|peak| (peak * scale).round().min(255.0) as u8)
+            .collect(),
+        duration_ms: ((pcm.len() as u64) * 1000) / (WHISPER_SAMPLE_RATE as u64),
+    })
+}
@@ -0,0 +1,5 @@
+pub mod signed_reply;
+pub mod signing_key;
+
+pub use signed_reply::*;
+pub use signing_key::*;
@@ -0,0 +1,18 @@
+use axum::{ body::{ Body, Bytes }, http::{ HeaderMap, StatusCode }, response::Response };
+
+/// Reply to a signed request, kept to answer the same request again without running it
+#[derive(Clone)]
+pub struct SignedReply {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl SignedReply {
+    pub fn to_response(&self) -> Response {
+        let mut response = Response::new(Body::from(self.body.clone()));
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers.clone();
+        response
+    }
+}
@@ -0,0 +1,33 @@
+use std::{ collections::HashMap, sync::{ Arc, Mutex } };
+
+use tokio::sync::OnceCell;
+
+use super::SignedReply;
+
+/// Reply to a request, shared by every request carrying the same nonce
+type ReplySlot = Arc<OnceCell<SignedReply>>;
+
+/// Key shared with the canister, with the nonces of the requests it signed lately
+pub struct SigningKey {
+    pub secret: String,
+    /// Reply to each nonce, with the unix time after which its request is too old to be
+    /// accepted anyway and the nonce can be forgotten
+    replies: Mutex<HashMap<String, (u64, ReplySlot)>>,
+}
+
+impl SigningKey {
+    pub fn new(secret: String) -> Self {
+        SigningKey { secret, replies: Mutex::new(HashMap::new()) }
+    }
+
+    /// Where the reply to the request carrying `nonce` goes
+    pub fn reply_slot(&self, nonce: &str, expires_at: u64, now: u64) -> ReplySlot {
+        let mut replies = self.replies.lock().unwrap();
+        replies.retain(|_, (expires_at, _)| *expires_at >= now);
+
+        let (_, slot) = replies
+            .entry(nonce.to_string())
+            .or_insert_with(|| (expires_at, Arc::new(OnceCell::new())));
+        Arc::clone(slot)
+    }
+}
@@ -0,0 +1,9 @@
+pub mod require_event_token;
+pub mod require_signature;
+pub mod sign_payload;
+pub mod signed_body;
+
+pub use require_event_token::*;
+pub use require_signature::*;
+pub use sign_payload::*;
+pub use signed_body::*;
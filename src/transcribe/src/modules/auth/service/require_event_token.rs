@@ -0,0 +1,57 @@
+use std::{ collections::HashMap, sync::Arc };
+
+use axum::{ extract::{ Path, Query, Request, State }, middleware::Next, response::Response };
+
+use super::verify_payload;
+use crate::modules::{ auth::SigningKey, error::ApiError, job::unix_now };
+
+/// Longest a token may be valid for, whatever expiry it was minted with
+const MAX_EVENT_TOKEN_TTL_SECS: u64 = 60 * 60;
+
+/// Let `/events/{job_id}` through with a `token` query parameter instead of signed headers
+///
+/// Browsers follow the stream with `EventSource`, which cannot set headers. The token is
+/// `<expires>.<hex HMAC-SHA256 of "events\n<job_id>\n<expires>">`, minted by whoever holds the
+/// key for a single job. It is only checked when the stream is opened.
+pub async fn require_event_token(
+    State(key): State<Arc<SigningKey>>,
+    Path(job_id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+    request: Request,
+    next: Next
+) -> Result<Response, ApiError> {
+    let token = query
+        .get("token")
+        .ok_or_else(|| ApiError::Unauthorized("Missing token parameter".to_string()))?;
+
+    verify_event_token(&key.secret, &job_id, token)?;
+
+    Ok(next.run(request).await)
+}
+
+/// What an event token signs, bound to one job so it cannot open another's stream
+pub fn event_token_message(job_id: &str, expires: u64) -> Vec<u8> {
+    format!("events\n{}\n{}", job_id, expires).into_bytes()
+}
+
+fn verify_event_token(key: &str, job_id: &str, token: &str) -> Result<(), ApiError> {
+    let invalid = || ApiError::Unauthorized("Invalid token".to_string());
+
+    let (expires, signature) = token.split_once('.').ok_or_else(invalid)?;
+    let expires: u64 = expires.parse().map_err(|_| invalid())?;
+    let signature = hex::decode(signature).map_err(|_| invalid())?;
+
+    let now = unix_now();
+    if expires < now {
+        return Err(ApiError::Unauthorized("Token expired".to_string()));
+    }
+    if expires > now + MAX_EVENT_TOKEN_TTL_SECS {
+        return Err(invalid());
+    }
+
+    if !verify_payload(key, &event_token_message(job_id, expires), &signature) {
+        return Err(invalid());
+    }
+
+    Ok(())
+}
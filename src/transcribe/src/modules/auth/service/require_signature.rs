@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use axum::{
+    body::{ to_bytes, Body },
+    extract::{ Request, State },
+    http::{ request::Parts, HeaderMap, Method },
+    middleware::Next,
+    response::Response,
+};
+
+use super::{ verify_payload, SignedBody };
+use crate::modules::{ auth::{ SignedReply, SigningKey }, error::ApiError, job::unix_now };
+
+/// Unix time in seconds at which the request was signed
+pub const TIMESTAMP_HEADER: &str = "X-Transcribe-Timestamp";
+/// Identifies a request, it is only run once however often it is sent
+pub const NONCE_HEADER: &str = "X-Transcribe-Nonce";
+/// Hex SHA-256 of the request body, covered by the signature in place of the body itself
+pub const CONTENT_SHA256_HEADER: &str = "X-Transcribe-Content-SHA256";
+/// Hex HMAC-SHA256 of the signed message, see `signed_message`
+pub const SIGNATURE_HEADER: &str = "X-Transcribe-Signature";
+
+/// How far the signing clock may be off, a request older than that is refused
+const MAX_CLOCK_SKEW_SECS: u64 = 300;
+/// Largest reply kept to answer a repeated request, the service's are far smaller
+const MAX_SIGNED_REPLY: usize = 2 * 1024 * 1024;
+
+/// Reject any request that is not signed with the shared key
+///
+/// The signature covers a digest of the body rather than the body, so it is checked from the
+/// headers alone, before the handler runs or anything is read. The body is then checked
+/// against the digest as it streams to the handler, which finds the `SignedBody` in the
+/// request extensions and must `verify` it before keeping what it read without reading it
+/// to the end.
+///
+/// A captured request cannot be replayed to the same effect: a request whose nonce was seen
+/// before is not run again, a read is answered afresh and anything else gets the reply the
+/// first one got. That is also what lets every replica of the canister's subnet send the
+/// same outcall.
+pub async fn require_signature(
+    State(key): State<Arc<SigningKey>>,
+    request: Request,
+    next: Next
+) -> Result<Response, ApiError> {
+    let headers = signature_headers(request.headers())?;
+
+    let (mut parts, body) = request.into_parts();
+    let message = signed_message(
+        headers.timestamp,
+        &headers.nonce,
+        parts.method.as_str(),
+        signed_path(&parts),
+        &hex::encode(&headers.digest)
+    );
+    if !verify_payload(&key.secret, &message, &headers.signature) {
+        return Err(request_rejected());
+    }
+
+    let signed = SignedBody::new(headers.digest, body);
+    parts.extensions.insert(signed.clone());
+
+    let read_only = parts.method == Method::GET || parts.method == Method::HEAD;
+    let request = Request::from_parts(parts, Body::from_stream(signed.clone()));
+
+    // Whatever the handler made of it, a body that turned out forged is refused
+    if read_only {
+        let response = next.run(request).await;
+        if signed.verified() == Some(false) {
+            return Err(request_rejected());
+        }
+        return Ok(response);
+    }
+
+    // A forged body leaves the slot empty, so it cannot spoil the genuine request's reply
+    let expires_at = headers.timestamp + MAX_CLOCK_SKEW_SECS;
+    let slot = key.reply_slot(&headers.nonce, expires_at, unix_now());
+    let reply = slot
+        .get_or_try_init(|| async {
+            let response = next.run(request).await;
+            if signed.verified() == Some(false) {
+                return Err(request_rejected());
+            }
+
+            let (parts, body) = response.into_parts();
+            let body = to_bytes(body, MAX_SIGNED_REPLY).await.map_err(|e| {
+                ApiError::Internal(format!("Failed to read reply: {}", e))
+            })?;
+            Ok(SignedReply { status: parts.status, headers: parts.headers, body })
+        }).await?;
+
+    Ok(reply.to_response())
+}
+
+/// What a request signature covers:
+/// `<timestamp>\n<nonce>\n<METHOD>\n<path?query>\n<hex SHA-256 of body>`
+pub fn signed_message(
+    timestamp: u64,
+    nonce: &str,
+    method: &str,
+    path: &str,
+    body_sha256: &str
+) -> Vec<u8> {
+    format!("{}\n{}\n{}\n{}\n{}", timestamp, nonce, method, path, body_sha256).into_bytes()
+}
+
+/// The one answer to a forged request, it tells nothing about what was wrong with it
+pub fn request_rejected() -> ApiError {
+    ApiError::Unauthorized("Request rejected".to_string())
+}
+
+fn signed_path(parts: &Parts) -> &str {
+    parts.uri
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/")
+}
+
+/// Signature headers of a request
+struct SignatureHeaders {
+    timestamp: u64,
+    nonce: String,
+    digest: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+/// Signature headers of a request, refused when any is missing, malformed or stale
+fn signature_headers(headers: &HeaderMap) -> Result<SignatureHeaders, ApiError> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| ApiError::Unauthorized(format!("Missing {} header", name)))
+    };
+    // A SHA-256 and a HMAC-SHA256 are both 32 bytes
+    let digest_header = |name: &str| {
+        hex::decode(header(name)?)
+            .ok()
+            .filter(|digest| digest.len() == 32)
+            .ok_or_else(|| ApiError::Unauthorized(format!("Invalid {} header", name)))
+    };
+
+    let timestamp: u64 = header(TIMESTAMP_HEADER)?
+        .parse()
+        .map_err(|_| ApiError::Unauthorized(format!("Invalid {} header", TIMESTAMP_HEADER)))?;
+    if timestamp.abs_diff(unix_now()) > MAX_CLOCK_SKEW_SECS {
+        return Err(
+            ApiError::Unauthorized("Request timestamp is too old or in the future".to_string())
+        );
+    }
+
+    let nonce = header(NONCE_HEADER)?;
+    let valid_nonce =
+        !nonce.is_empty() &&
+        nonce.len() <= 64 &&
+        nonce.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid_nonce {
+        return Err(ApiError::Unauthorized(format!("Invalid {} header", NONCE_HEADER)));
+    }
+
+    Ok(SignatureHeaders {
+        timestamp,
+        nonce: nonce.to_string(),
+        digest: digest_header(CONTENT_SHA256_HEADER)?,
+        signature: digest_header(SIGNATURE_HEADER)?,
+    })
+}
@@ -0,0 +1,22 @@
+use hmac::{ Hmac, Mac };
+use sha2::Sha256;
+
+/// Hex encoded HMAC-SHA256 of a payload
+pub fn sign_payload(secret: &str, payload: &[u8]) -> String {
+    let mut mac = payload_mac(secret);
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Check a HMAC-SHA256 in constant time
+pub fn verify_payload(secret: &str, payload: &[u8], signature: &[u8]) -> bool {
+    let mut mac = payload_mac(secret);
+    mac.update(payload);
+    mac.verify_slice(signature).is_ok()
+}
+
+/// HMAC-SHA256 keyed with a secret
+fn payload_mac(secret: &str) -> Hmac<Sha256> {
+    // HMAC takes keys of any length, this cannot fail
+    Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key")
+}
@@ -0,0 +1,103 @@
+use std::pin::Pin;
+use std::sync::{ Arc, Mutex };
+use std::task::{ Context, Poll };
+
+use axum::body::{ Body, BodyDataStream, Bytes };
+use futures_util::{ Stream, StreamExt };
+use sha2::{ Digest, Sha256 };
+
+use super::request_rejected;
+use crate::modules::error::ApiError;
+
+/// Body of a signed request, checked against the digest its signature covers as it streams
+/// to the handler
+///
+/// The signature itself is checked from the headers before anything is read, the body is
+/// hashed a piece at a time so a large upload is never held in memory. Whether it matches is
+/// only known once the whole body went through: a handler acting on a body it does not read
+/// to the end itself, such as a multipart upload, has to `verify` it before keeping anything.
+#[derive(Clone)]
+pub struct SignedBody {
+    state: Arc<Mutex<SignedBodyState>>,
+}
+
+struct SignedBodyState {
+    body: BodyDataStream,
+    /// Taken once the body ended and its digest was checked
+    hasher: Option<Sha256>,
+    digest: Vec<u8>,
+    verified: Option<bool>,
+}
+
+impl SignedBody {
+    /// `digest` is the SHA-256 of the body the signature was made over
+    pub fn new(digest: Vec<u8>, body: Body) -> Self {
+        SignedBody {
+            state: Arc::new(
+                Mutex::new(SignedBodyState {
+                    body: body.into_data_stream(),
+                    hasher: Some(Sha256::new()),
+                    digest,
+                    verified: None,
+                })
+            ),
+        }
+    }
+
+    /// Whether the body matched its digest, `None` while it has not been read to the end
+    pub fn verified(&self) -> Option<bool> {
+        self.state.lock().unwrap().verified
+    }
+
+    /// Read whatever the handler left of the body, then check the digest of all of it
+    pub async fn verify(&self) -> Result<(), ApiError> {
+        let mut rest = self.clone();
+        while let Some(piece) = rest.next().await {
+            if piece.is_err() {
+                break;
+            }
+        }
+
+        match self.verified() {
+            Some(true) => Ok(()),
+            Some(false) => Err(request_rejected()),
+            None => Err(ApiError::BadRequest("Request body ended early".to_string())),
+        }
+    }
+}
+
+impl Stream for SignedBody {
+    type Item = Result<Bytes, axum::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+
+        if let Some(verified) = state.verified {
+            return Poll::Ready((!verified).then(invalid_body));
+        }
+
+        match state.body.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(piece))) => {
+                if let Some(hasher) = &mut state.hasher {
+                    hasher.update(&piece);
+                }
+                Poll::Ready(Some(Ok(piece)))
+            }
+            Poll::Ready(None) => {
+                let verified = state.hasher
+                    .take()
+                    .is_some_and(|hasher| hasher.finalize().as_slice() == state.digest);
+                state.verified = Some(verified);
+
+                // A forged body ends in an error, so a handler reading it to the end fails
+                Poll::Ready((!verified).then(invalid_body))
+            }
+            other => other,
+        }
+    }
+}
+
+fn invalid_body() -> Result<Bytes, axum::Error> {
+    Err(axum::Error::new("Request body does not match its signature"))
+}
@@ -0,0 +1,5 @@
+pub mod probe_file;
+pub mod transcribe_files;
+
+pub use probe_file::*;
+pub use transcribe_files::*;
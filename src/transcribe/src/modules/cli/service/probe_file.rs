@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use anyhow::Result;
+use serde_json::json;
+
+use crate::modules::{ audio, config::Config };
+
+/// Decode a file and print, as JSON, its streams and what a job would hand to whisper
+///
+/// Useful to reproduce decoding and VAD problems from a bug report without running a model.
+pub fn probe_file(config: &Config, file: &Path) -> Result<()> {
+    let media = audio::probe_media(file, config.ffmpeg_fallback)?;
+    let pcm = audio::decode_media(file, config.ffmpeg_fallback)?;
+    let speech = audio::detect_speech(&pcm);
+    let windows = audio::speech_windows(&pcm, config.vad, config.max_window_secs);
+
+    let secs = |samples: usize| (samples as f64) / (audio::WHISPER_SAMPLE_RATE as f64);
+    let speech_samples: usize = speech
+        .iter()
+        .map(|r| r.len())
+        .sum();
+
+    let windows: Vec<_> = windows
+        .iter()
+        .map(|w| json!({ "start_secs": secs(w.start), "end_secs": secs(w.end) }))
+        .collect();
+
+    let report = json!({
+        "file": file.display().to_string(),
+        "media": media,
+        "duration_secs": secs(pcm.len()),
+        "speech_secs": secs(speech_samples),
+        "speech_regions": speech.len(),
+        "vad": config.vad,
+        "windows": windows,
+    });
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    Ok(())
+}
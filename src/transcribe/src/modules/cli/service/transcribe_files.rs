@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{ Path, PathBuf };
+use std::sync::{ atomic::AtomicBool, Arc };
+
+use anyhow::{ anyhow, Result };
+
+use crate::modules::{
+    config::{ Config, TranscribeCommand },
+    engine::Transcriber,
+    export::{ render_transcript, ExportFormat },
+    whisper::{ self, TranscriptionOptions },
+};
+
+/// Transcribe files one after the other, the same way a job of the HTTP service would
+///
+/// Results are written next to each input, `talk.mp4` gives `talk.mp4.json`, `talk.mp4.srt`, ...
+/// A file that fails is reported and skipped, so one bad file does not stop a whole batch. So is
+/// a file whose results would overwrite one of the inputs or a result written earlier in the run.
+pub fn transcribe_files(
+    engine: &impl Transcriber,
+    config: &Config,
+    command: TranscribeCommand
+) -> Result<()> {
+    let options = command.options.into_options().map_err(|e| anyhow!(e))?;
+
+    let model = engine.resolve_model(command.model.as_deref()).map_err(|e| anyhow!(e))?;
+    engine.load_model(&model)?;
+
+    // `--format srt,srt` writes the file once
+    let mut formats: Vec<ExportFormat> = Vec::new();
+    for format in &command.format {
+        if !formats.contains(format) {
+            formats.push(*format);
+        }
+    }
+
+    let inputs: HashSet<PathBuf> = command.files
+        .iter()
+        .map(std::path::absolute)
+        .collect::<Result<_, _>>()?;
+    let mut written: HashSet<PathBuf> = HashSet::new();
+
+    let mut failed = 0;
+    for file in &command.files {
+        eprintln!("Transcribing {} with {}", file.display(), model);
+
+        let outputs = match output_paths(file, &formats, &inputs, &written) {
+            Ok(outputs) => outputs,
+            Err(e) => {
+                eprintln!("Skipping {}: {}", file.display(), e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        match transcribe_file(engine, config, &model, &options, file, &outputs, &mut written) {
+            Ok(()) => {}
+            Err(e) => {
+                eprintln!("Failed to transcribe {}: {}", file.display(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow!("{} of {} files failed", failed, command.files.len()));
+    }
+
+    Ok(())
+}
+
+fn transcribe_file(
+    engine: &impl Transcriber,
+    config: &Config,
+    model: &str,
+    options: &TranscriptionOptions,
+    file: &Path,
+    outputs: &[(ExportFormat, PathBuf)],
+    written: &mut HashSet<PathBuf>
+) -> Result<()> {
+    let pcm = engine.decode(file)?;
+
+    let run = whisper::TranscribeRun {
+        n_threads: config.threads_per_job,
+        parallel: config.parallel_windows,
+        on_progress: Arc::new(|fraction: f32| {
+            eprint!("\r{:3}%", (fraction * 100.0).round() as u32);
+            let _ = std::io::stderr().flush();
+        }),
+        on_segment: Arc::new(|_| {}),
+        cancelled: Arc::new(AtomicBool::new(false)),
+    };
+
+    let mut result = engine.transcribe(model, &pcm, options, &run)?;
+    result.model = Some(model.to_string());
+    eprintln!();
+
+    for (format, output) in outputs {
+        fs::write(output, render_transcript(&result, *format)?)
+            .map_err(|e| anyhow!("Failed to write {}: {}", output.display(), e))?;
+        written.insert(output.clone());
+        eprintln!("Wrote {}", output.display());
+    }
+
+    Ok(())
+}
+
+/// Where the results of a file go, its whole name with the format's extension appended
+///
+/// `talk.mp4` and `talk.wav` would both give `talk.srt` if the extension were replaced, and
+/// `notes` would be overwritten by the text export of `notes.mp4`.
+fn output_paths(
+    file: &Path,
+    formats: &[ExportFormat],
+    inputs: &HashSet<PathBuf>,
+    written: &HashSet<PathBuf>
+) -> Result<Vec<(ExportFormat, PathBuf)>> {
+    let file = std::path::absolute(file)?;
+    let name = file
+        .file_name()
+        .ok_or_else(|| anyhow!("{} does not name a file", file.display()))?;
+
+    formats
+        .iter()
+        .map(|format| {
+            let mut output_name = name.to_os_string();
+            output_name.push(".");
+            output_name.push(format.extension());
+            let output = file.with_file_name(output_name);
+
+            if inputs.contains(&output) {
+                return Err(anyhow!("{} is one of the inputs", output.display()));
+            }
+            if written.contains(&output) {
+                return Err(anyhow!("{} was already written in this run", output.display()));
+            }
+            Ok((*format, output))
+        })
+        .collect()
+}
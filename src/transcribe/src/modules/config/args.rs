@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use crate::modules::engine::EngineKind;
+
+use super::Command;
+
+/// Command line flags, each one can also be given as an environment variable
+#[derive(Parser, Debug, Default)]
+#[command(name = "transcribe", about = "Whisper transcription service")]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// TOML config file, overridden by any flag or environment variable
+    #[arg(long, global = true, env = "TRANSCRIBE_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Journal directory for jobs and upload sessions
+    #[arg(long, global = true, env = "TRANSCRIBE_DATA_DIR")]
+    pub data_dir: Option<String>,
+
+    /// Directory containing the ggml-*.bin model files
+    #[arg(long, global = true, env = "TRANSCRIBE_MODELS_DIR")]
+    pub models_dir: Option<String>,
+
+    /// Model used when a job does not ask for one
+    #[arg(long, global = true, env = "TRANSCRIBE_DEFAULT_MODEL")]
+    pub default_model: Option<String>,
+
+    /// Number of jobs transcribed at the same time
+    #[arg(long, global = true, env = "TRANSCRIBE_WORKERS")]
+    pub workers: Option<usize>,
+
+    /// Whisper threads used by a single job
+    #[arg(long, global = true, env = "TRANSCRIBE_THREADS")]
+    pub threads: Option<usize>,
+
+    /// Jobs that may wait for a free worker before new ones are rejected
+    #[arg(long, global = true, env = "TRANSCRIBE_QUEUE_SIZE")]
+    pub queue_size: Option<usize>,
+
+    /// Retry with the ffmpeg binary when a file cannot be decoded natively
+    #[arg(long, global = true, env = "TRANSCRIBE_FFMPEG_FALLBACK", action = clap::ArgAction::Set)]
+    pub ffmpeg_fallback: Option<bool>,
+
+    /// Skip silence and split the audio on pauses before transcribing
+    #[arg(long, global = true, env = "TRANSCRIBE_VAD", action = clap::ArgAction::Set)]
+    pub vad: Option<bool>,
+
+    /// Longest stretch of audio given to whisper in one go, in seconds
+    #[arg(long, global = true, env = "TRANSCRIBE_MAX_WINDOW_SECS")]
+    pub max_window_secs: Option<u64>,
+
+    /// Windows of a single job transcribed at the same time, sharing the job's threads
+    #[arg(long, global = true, env = "TRANSCRIBE_PARALLEL_WINDOWS")]
+    pub parallel_windows: Option<usize>,
+
+    /// Largest upload accepted for a single session, in megabytes
+    #[arg(long, global = true, env = "TRANSCRIBE_MAX_SESSION_MB")]
+    pub max_session_mb: Option<u64>,
+
+    /// Seconds a finished job and its result are kept
+    #[arg(long, global = true, env = "TRANSCRIBE_JOB_TTL_SECS")]
+    pub job_ttl_secs: Option<u64>,
+
+    /// Seconds an upload session may go without a new chunk before it is dropped
+    #[arg(long, global = true, env = "TRANSCRIBE_SESSION_TTL_SECS")]
+    pub session_ttl_secs: Option<u64>,
+
+    /// Engine jobs are transcribed with, `mock` returns a scripted transcript without any model
+    #[arg(long, global = true, value_enum, env = "TRANSCRIBE_ENGINE")]
+    pub engine: Option<EngineKind>,
+
+    /// JSON file with the transcript returned by the mock engine, a built-in one without it
+    #[arg(long, global = true, env = "TRANSCRIBE_MOCK_SCRIPT")]
+    pub mock_script: Option<PathBuf>,
+
+    /// Shared secret every request must be signed with, requests are not checked without it
+    #[arg(long, global = true, env = "TRANSCRIBE_SIGNING_KEY", hide_env_values = true)]
+    pub signing_key: Option<String>,
+}
@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use clap::{ Args as ClapArgs, Subcommand };
+
+use crate::modules::{ export::ExportFormat, whisper::TranscriptionOptions };
+
+/// What the binary does, serving the HTTP API when no subcommand is given
+#[derive(Subcommand, Debug, Clone, Default)]
+pub enum Command {
+    /// Run the HTTP service
+    #[default]
+    Serve,
+    /// Transcribe files and write the results next to each of them
+    Transcribe(TranscribeCommand),
+    /// Decode a file and report what the transcription would work on
+    Probe {
+        file: PathBuf,
+    },
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct TranscribeCommand {
+    #[arg(required = true)]
+    pub files: Vec<PathBuf>,
+
+    /// Output formats, written as `<file>.<format>`
+    #[arg(long, value_enum, value_delimiter = ',', default_value = "json")]
+    pub format: Vec<ExportFormat>,
+
+    /// Model to transcribe with instead of the default one
+    #[arg(long)]
+    pub model: Option<String>,
+
+    #[command(flatten)]
+    pub options: OptionArgs,
+}
+
+/// Same settings as the `options` object of `/finalize_upload`
+#[derive(ClapArgs, Debug, Clone)]
+pub struct OptionArgs {
+    /// ISO 639-1 code of the spoken language, detected when missing
+    #[arg(long)]
+    pub language: Option<String>,
+
+    /// Translate the speech to English
+    #[arg(long)]
+    pub translate: bool,
+
+    /// Text fed to the decoder before the audio, for custom vocabulary and spelling
+    #[arg(long)]
+    pub initial_prompt: Option<String>,
+
+    /// Decode with beam search of this width instead of greedily
+    #[arg(long)]
+    pub beam_size: Option<u32>,
+
+    #[arg(long)]
+    pub best_of: Option<u32>,
+
+    #[arg(long)]
+    pub temperature: Option<f32>,
+
+    #[arg(long)]
+    pub temperature_increment: Option<f32>,
+
+    /// Label the speaker of every segment
+    #[arg(long)]
+    pub diarize: bool,
+
+    #[arg(long)]
+    pub max_speakers: Option<u32>,
+}
+
+impl OptionArgs {
+    pub fn into_options(self) -> Result<TranscriptionOptions, String> {
+        let defaults = TranscriptionOptions::default();
+        let options = TranscriptionOptions {
+            language: self.language,
+            translate: self.translate,
+            initial_prompt: self.initial_prompt,
+            beam_size: self.beam_size,
+            best_of: self.best_of.unwrap_or(defaults.best_of),
+            temperature: self.temperature.unwrap_or(defaults.temperature),
+            temperature_increment: self.temperature_increment.unwrap_or(
+                defaults.temperature_increment
+            ),
+            diarize: self.diarize,
+            max_speakers: self.max_speakers,
+        };
+        options.validate()?;
+
+        Ok(options)
+    }
+}
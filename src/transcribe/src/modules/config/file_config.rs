@@ -0,0 +1,38 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use anyhow::{ anyhow, Result };
+use serde::Deserialize;
+
+use crate::modules::engine::EngineKind;
+
+/// Contents of the TOML config file, every key is optional
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileConfig {
+    pub data_dir: Option<String>,
+    pub models_dir: Option<String>,
+    pub default_model: Option<String>,
+    pub workers: Option<usize>,
+    pub threads: Option<usize>,
+    pub queue_size: Option<usize>,
+    pub ffmpeg_fallback: Option<bool>,
+    pub vad: Option<bool>,
+    pub max_window_secs: Option<u64>,
+    pub parallel_windows: Option<usize>,
+    pub max_session_mb: Option<u64>,
+    pub job_ttl_secs: Option<u64>,
+    pub session_ttl_secs: Option<u64>,
+    pub engine: Option<EngineKind>,
+    pub mock_script: Option<PathBuf>,
+    pub signing_key: Option<String>,
+}
+
+impl FileConfig {
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = fs
+            ::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read config {}: {}", path.display(), e))?;
+        toml::from_str(&content).map_err(|e| anyhow!("Invalid config {}: {}", path.display(), e))
+    }
+}
@@ -0,0 +1,125 @@
+pub mod args;
+pub mod command;
+pub mod file_config;
+
+pub use args::*;
+pub use command::*;
+pub use file_config::*;
+
+use std::path::PathBuf;
+use std::thread;
+
+use anyhow::Result;
+use clap::Parser;
+
+use crate::modules::engine::EngineKind;
+
+const DEFAULT_DATA_DIR: &str = "src/transcribe/data";
+const DEFAULT_MODELS_DIR: &str = "src/transcribe/assets/models";
+const DEFAULT_MODEL: &str = "base";
+const DEFAULT_THREADS_PER_JOB: usize = 4;
+const DEFAULT_QUEUE_SIZE: usize = 64;
+const DEFAULT_MAX_WINDOW_SECS: u64 = 120;
+const DEFAULT_MAX_SESSION_MB: u64 = 2048;
+const DEFAULT_JOB_TTL_SECS: u64 = 24 * 60 * 60;
+const DEFAULT_SESSION_TTL_SECS: u64 = 60 * 60;
+
+/// Service settings
+///
+/// Each value comes from the first source that sets it:
+/// command line flag, `TRANSCRIBE_*` environment variable, config file, built-in default.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub command: Command,
+    /// Journal directory for jobs and upload sessions
+    pub data_dir: String,
+    /// Directory containing the ggml-*.bin model files
+    pub models_dir: String,
+    /// Model used when a job does not ask for one, loaded at startup
+    pub default_model: String,
+    /// Number of jobs transcribed at the same time
+    pub workers: usize,
+    /// Whisper threads used by a single job
+    pub threads_per_job: usize,
+    /// Jobs that may wait for a free worker before new ones are rejected
+    pub queue_size: usize,
+    /// Retry with the ffmpeg binary when a file cannot be decoded natively
+    pub ffmpeg_fallback: bool,
+    /// Skip silence and split the audio on pauses before transcribing
+    pub vad: bool,
+    /// Longest stretch of audio given to whisper in one go
+    pub max_window_secs: u64,
+    /// Windows of a single job transcribed at the same time, sharing the job's threads
+    pub parallel_windows: usize,
+    /// Largest upload accepted for a single session, in bytes
+    pub max_session_bytes: u64,
+    /// How long a finished job and its result are kept
+    pub job_ttl_secs: u64,
+    /// How long an upload session may go without a new chunk before it is dropped
+    pub session_ttl_secs: u64,
+    /// Engine jobs are transcribed with
+    pub engine: EngineKind,
+    /// Transcript returned by the mock engine, the built-in one when `None`
+    pub mock_script: Option<PathBuf>,
+    /// Shared secret every request must be signed with, `None` accepts unsigned requests
+    pub signing_key: Option<String>,
+}
+
+impl Config {
+    /// Settings from the command line, tests build theirs with `from_args`
+    pub fn load() -> Result<Self> {
+        Config::from_args(Args::parse())
+    }
+
+    /// Settings from already parsed flags, with the config file they point to
+    pub fn from_args(args: Args) -> Result<Self> {
+        let file = match &args.config {
+            Some(path) => FileConfig::read(path)?,
+            None => FileConfig::default(),
+        };
+
+        Ok(Config::merge(args, file))
+    }
+
+    fn merge(args: Args, file: FileConfig) -> Self {
+        let threads_per_job = args.threads.or(file.threads).unwrap_or(DEFAULT_THREADS_PER_JOB).max(1);
+
+        // By default spread the available cores over as many jobs as fit
+        let cores = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        let default_workers = (cores / threads_per_job).max(1);
+
+        Config {
+            command: args.command.unwrap_or_default(),
+            data_dir: args.data_dir.or(file.data_dir).unwrap_or_else(|| DEFAULT_DATA_DIR.to_string()),
+            models_dir: args.models_dir
+                .or(file.models_dir)
+                .unwrap_or_else(|| DEFAULT_MODELS_DIR.to_string()),
+            default_model: args.default_model
+                .or(file.default_model)
+                .unwrap_or_else(|| DEFAULT_MODEL.to_string()),
+            workers: args.workers.or(file.workers).unwrap_or(default_workers).max(1),
+            threads_per_job,
+            queue_size: args.queue_size.or(file.queue_size).unwrap_or(DEFAULT_QUEUE_SIZE).max(1),
+            ffmpeg_fallback: args.ffmpeg_fallback.or(file.ffmpeg_fallback).unwrap_or(true),
+            vad: args.vad.or(file.vad).unwrap_or(true),
+            max_window_secs: args.max_window_secs
+                .or(file.max_window_secs)
+                .unwrap_or(DEFAULT_MAX_WINDOW_SECS)
+                .max(1),
+            parallel_windows: args.parallel_windows.or(file.parallel_windows).unwrap_or(1).max(1),
+            max_session_bytes: args.max_session_mb
+                .or(file.max_session_mb)
+                .unwrap_or(DEFAULT_MAX_SESSION_MB)
+                .saturating_mul(1024 * 1024),
+            job_ttl_secs: args.job_ttl_secs.or(file.job_ttl_secs).unwrap_or(DEFAULT_JOB_TTL_SECS),
+            session_ttl_secs: args.session_ttl_secs
+                .or(file.session_ttl_secs)
+                .unwrap_or(DEFAULT_SESSION_TTL_SECS),
+            engine: args.engine.or(file.engine).unwrap_or_default(),
+            mock_script: args.mock_script.or(file.mock_script),
+            signing_key: args.signing_key.or(file.signing_key).filter(|key| !key.is_empty()),
+        }
+    }
+}
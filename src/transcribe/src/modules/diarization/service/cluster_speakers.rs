@@ -0,0 +1,129 @@
+/// Most voice prints clustered pair by pair, which costs the cube of their number
+const MAX_CLUSTERED: usize = 256;
+
+/// Group voice prints into speakers with average-linkage clustering on cosine similarity
+///
+/// Clusters keep merging while they are more alike than `threshold`, and in any case until at
+/// most `max_speakers` remain. Past `MAX_CLUSTERED` prints, only prints spread evenly over the
+/// recording are clustered and every other one joins the speaker whose average print is nearest.
+/// Returns a speaker index per print, numbered by first appearance.
+pub fn cluster_speakers(prints: &[Vec<f32>], max_speakers: usize, threshold: f32) -> Vec<usize> {
+    let stride = prints.len().div_ceil(MAX_CLUSTERED).max(1);
+    let sampled: Vec<&[f32]> = prints.iter().step_by(stride).map(Vec::as_slice).collect();
+    let sampled_clusters = number_by_appearance(
+        &agglomerate(&sampled, max_speakers, threshold)
+    );
+
+    // Cosine ignores scale, so the sum of a cluster's prints stands for their average
+    let clusters = sampled_clusters.iter().max().map_or(0, |last| last + 1);
+    let dims = prints.first().map_or(0, Vec::len);
+    let mut centroids = vec![vec![0.0f32; dims]; clusters];
+    for (print, &cluster) in sampled.iter().zip(&sampled_clusters) {
+        for (sum, value) in centroids[cluster].iter_mut().zip(print.iter()) {
+            *sum += value;
+        }
+    }
+
+    let cluster_of: Vec<usize> = prints
+        .iter()
+        .enumerate()
+        .map(|(i, print)| {
+            if i % stride == 0 {
+                return sampled_clusters[i / stride];
+            }
+            let similarity = |cluster: &usize| cosine(print, &centroids[*cluster]);
+            (0..clusters)
+                .max_by(|a, b| similarity(a).total_cmp(&similarity(b)))
+                .unwrap_or(0)
+        })
+        .collect();
+
+    number_by_appearance(&cluster_of)
+}
+
+/// Average-linkage clustering of every print against every other, returning a cluster id each
+fn agglomerate(prints: &[&[f32]], max_speakers: usize, threshold: f32) -> Vec<usize> {
+    let n = prints.len();
+    let max_speakers = max_speakers.max(1);
+
+    let mut similarity: Vec<Vec<f32>> = prints
+        .iter()
+        .map(|a| prints.iter().map(|b| cosine(a, b)).collect())
+        .collect();
+    let mut members: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    let mut alive = vec![true; n];
+    let mut remaining = n;
+
+    while remaining > 1 {
+        let mut best: Option<(usize, usize, f32)> = None;
+        for i in (0..n).filter(|&i| alive[i]) {
+            for j in (i + 1..n).filter(|&j| alive[j]) {
+                if best.is_none_or(|(_, _, s)| similarity[i][j] > s) {
+                    best = Some((i, j, similarity[i][j]));
+                }
+            }
+        }
+
+        let Some((i, j, score)) = best else {
+            break;
+        };
+        if score < threshold && remaining <= max_speakers {
+            break;
+        }
+
+        // Average linkage, weighted by how many prints each side already holds
+        let (size_i, size_j) = (members[i].len() as f32, members[j].len() as f32);
+        for k in (0..n).filter(|&k| alive[k] && k != i && k != j) {
+            let merged = (size_i * similarity[i][k] + size_j * similarity[j][k]) / (size_i + size_j);
+            similarity[i][k] = merged;
+            similarity[k][i] = merged;
+        }
+
+        let moved = std::mem::take(&mut members[j]);
+        members[i].extend(moved);
+        alive[j] = false;
+        remaining -= 1;
+    }
+
+    let mut cluster_of = vec![0; n];
+    for (cluster, indices) in members.iter().enumerate() {
+        for &index in indices {
+            cluster_of[index] = cluster;
+        }
+    }
+
+    cluster_of
+}
+
+/// Renumber clusters so the first one heard is 0, the next new one 1, and so on
+fn number_by_appearance(cluster_of: &[usize]) -> Vec<usize> {
+    let mut order: Vec<usize> = Vec::new();
+    cluster_of
+        .iter()
+        .map(|cluster| {
+            order
+                .iter()
+                .position(|c| c == cluster)
+                .unwrap_or_else(|| {
+                    order.push(*cluster);
+                    order.len() - 1
+                })
+        })
+        .collect()
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a
+        .iter()
+        .zip(b)
+        .map(|(x, y)| x * y)
+        .sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
@@ -0,0 +1,7 @@
+pub mod cluster_speakers;
+pub mod diarize;
+pub mod voice_print;
+
+pub use cluster_speakers::*;
+pub use diarize::*;
+pub use voice_print::*;
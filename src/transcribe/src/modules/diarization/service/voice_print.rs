@@ -0,0 +1,152 @@
+use std::f32::consts::PI;
+
+use crate::modules::audio::WHISPER_SAMPLE_RATE;
+
+const FRAME_SIZE: usize = 512;
+const HOP_SIZE: usize = 256;
+const MEL_BANDS: usize = 24;
+const MIN_FREQ: f32 = 100.0;
+const MAX_FREQ: f32 = 7600.0;
+/// Frames quieter than this are treated as silence and left out of the print
+const SILENCE_RMS: f32 = 0.01;
+/// About 30 seconds of audio, plenty to characterise a voice
+const MAX_FRAMES: usize = 2000;
+
+/// Average log-mel spectrum of the voiced frames of a span of mono 16kHz samples
+///
+/// The mean of the bands is removed so the print describes the timbre of the voice rather than
+/// how loud it was recorded. Returns `None` when the span holds no usable speech.
+pub fn voice_print(pcm: &[f32], start_ms: u64, end_ms: u64) -> Option<Vec<f32>> {
+    let start = ms_to_sample(start_ms).min(pcm.len());
+    let end = ms_to_sample(end_ms).min(pcm.len());
+    if end < start + FRAME_SIZE {
+        return None;
+    }
+
+    let window: Vec<f32> = (0..FRAME_SIZE)
+        .map(|i| 0.5 - 0.5 * ((2.0 * PI * (i as f32)) / (FRAME_SIZE as f32)).cos())
+        .collect();
+    let filters = mel_filters();
+
+    let mut sums = [0.0f32; MEL_BANDS];
+    let mut frames = 0;
+    let mut re = vec![0.0f32; FRAME_SIZE];
+    let mut im = vec![0.0f32; FRAME_SIZE];
+
+    for offset in (start..=end - FRAME_SIZE).step_by(HOP_SIZE).take(MAX_FRAMES) {
+        let frame = &pcm[offset..offset + FRAME_SIZE];
+
+        let rms = (
+            frame
+                .iter()
+                .map(|s| s * s)
+                .sum::<f32>() / (FRAME_SIZE as f32)
+        ).sqrt();
+        if rms < SILENCE_RMS {
+            continue;
+        }
+
+        for ((r, i), (sample, w)) in re.iter_mut().zip(im.iter_mut()).zip(frame.iter().zip(&window)) {
+            *r = sample * w;
+            *i = 0.0;
+        }
+        fft(&mut re, &mut im);
+
+        for (sum, filter) in sums.iter_mut().zip(&filters) {
+            let energy: f32 = filter
+                .iter()
+                .map(|&(bin, weight)| weight * (re[bin] * re[bin] + im[bin] * im[bin]))
+                .sum();
+            *sum += (energy + 1e-10).ln();
+        }
+        frames += 1;
+    }
+
+    if frames == 0 {
+        return None;
+    }
+
+    let mut print: Vec<f32> = sums
+        .iter()
+        .map(|s| s / (frames as f32))
+        .collect();
+    let mean = print.iter().sum::<f32>() / (MEL_BANDS as f32);
+    print.iter_mut().for_each(|v| {
+        *v -= mean;
+    });
+
+    Some(print)
+}
+
+fn ms_to_sample(ms: u64) -> usize {
+    ((ms * (WHISPER_SAMPLE_RATE as u64)) / 1000) as usize
+}
+
+/// Triangular filters evenly spaced on the mel scale, as `(fft bin, weight)` pairs
+fn mel_filters() -> Vec<Vec<(usize, f32)>> {
+    let to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let to_hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+
+    let bin_hz = (WHISPER_SAMPLE_RATE as f32) / (FRAME_SIZE as f32);
+    let (low, high) = (to_mel(MIN_FREQ), to_mel(MAX_FREQ));
+    let edges: Vec<f32> = (0..MEL_BANDS + 2)
+        .map(|i| to_hz(low + ((high - low) * (i as f32)) / ((MEL_BANDS + 1) as f32)))
+        .collect();
+
+    edges
+        .windows(3)
+        .map(|edge| {
+            (0..=FRAME_SIZE / 2)
+                .filter_map(|bin| {
+                    let hz = (bin as f32) * bin_hz;
+                    let weight = if hz <= edge[1] {
+                        (hz - edge[0]) / (edge[1] - edge[0])
+                    } else {
+                        (edge[2] - hz) / (edge[2] - edge[1])
+                    };
+                    (weight > 0.0).then_some((bin, weight))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// In-place radix-2 FFT, the length must be a power of two
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = (-2.0 * PI) / (len as f32);
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * (k as f32)).sin_cos();
+                let a = start + k;
+                let b = a + len / 2;
+
+                let tr = re[b] * cos - im[b] * sin;
+                let ti = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+}
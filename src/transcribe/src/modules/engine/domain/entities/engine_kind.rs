@@ -0,0 +1,13 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// Engine jobs are transcribed with
+#[derive(Deserialize, ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EngineKind {
+    /// whisper.cpp with the ggml models of the models directory
+    #[default]
+    Whisper,
+    /// Scripted transcripts, for testing without models or ffmpeg
+    Mock,
+}
@@ -0,0 +1,56 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{ anyhow, Result };
+use serde::Deserialize;
+
+use super::MockSegment;
+
+/// Transcript returned by the mock engine for every job, read from a JSON file
+#[derive(Deserialize, Debug, Clone)]
+pub struct MockScript {
+    /// Language reported when the job does not ask for one
+    #[serde(default = "default_language")]
+    pub language: String,
+    pub segments: Vec<MockSegment>,
+}
+
+impl MockScript {
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = fs
+            ::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read mock script {}: {}", path.display(), e))?;
+        serde_json
+            ::from_str(&content)
+            .map_err(|e| anyhow!("Invalid mock script {}: {}", path.display(), e))
+    }
+
+    /// End of the last segment, `None` for an empty script
+    pub fn duration_ms(&self) -> Option<u64> {
+        self.segments.iter().map(|s| s.end_ms).max()
+    }
+}
+
+impl Default for MockScript {
+    fn default() -> Self {
+        let segment = |start_ms: u64, end_ms: u64, text: &str| MockSegment {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+            speaker: None,
+        };
+
+        MockScript {
+            language: default_language(),
+            segments: vec![
+                segment(0, 2400, "Hello and welcome to Transkripin."),
+                segment(2400, 5200, "This transcript comes from the mock engine."),
+                segment(5200, 8000, "It is the same for every file.")
+            ],
+        }
+    }
+}
+
+fn default_language() -> String {
+    "en".to_string()
+}
@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+/// A segment the mock engine returns as-is
+#[derive(Deserialize, Debug, Clone)]
+pub struct MockSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+    /// Speaker given to the segment when diarization is requested, alternating when left out
+    #[serde(default)]
+    pub speaker: Option<String>,
+}
@@ -0,0 +1,7 @@
+pub mod engine_kind;
+pub mod mock_script;
+pub mod mock_segment;
+
+pub use engine_kind::*;
+pub use mock_script::*;
+pub use mock_segment::*;
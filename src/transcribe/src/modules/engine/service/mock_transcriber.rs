@@ -0,0 +1,158 @@
+use std::path::Path;
+use std::sync::atomic::Ordering;
+
+use anyhow::{ anyhow, Result };
+
+use crate::modules::{
+    audio::{ MediaInfo, MediaStream, StreamKind, WHISPER_SAMPLE_RATE },
+    engine::{ MockScript, MockSegment },
+    model::ModelInfo,
+    whisper::{
+        TranscribeRun,
+        TranscriptionOptions,
+        TranscriptionResponse,
+        TranscriptionSegment,
+        TranscriptionWord,
+    },
+};
+
+use super::Transcriber;
+
+/// Returns the same scripted transcript for any upload, without models or decoding
+///
+/// Lets the upload, job and result flow of the service be run end to end on a machine
+/// without whisper models or ffmpeg. Any model name is accepted.
+pub struct MockTranscriber {
+    script: MockScript,
+    default_model: String,
+}
+
+impl MockTranscriber {
+    pub fn new(script: MockScript, default_model: impl Into<String>) -> Self {
+        MockTranscriber { script, default_model: default_model.into() }
+    }
+}
+
+impl Transcriber for MockTranscriber {
+    fn default_model(&self) -> &str {
+        &self.default_model
+    }
+
+    fn list_models(&self) -> Vec<ModelInfo> {
+        vec![ModelInfo {
+            name: self.default_model.clone(),
+            file: "mock".to_string(),
+            size_mb: 0,
+            quantization: None,
+            english_only: false,
+            available: true,
+            loaded: true,
+        }]
+    }
+
+    fn resolve_model(&self, name: Option<&str>) -> Result<String, String> {
+        Ok(name.unwrap_or(&self.default_model).to_string())
+    }
+
+    fn load_model(&self, _name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// A single audio stream as long as the script
+    fn probe(&self, media: &Path) -> Result<MediaInfo> {
+        let duration_ms = self.script.duration_ms();
+
+        Ok(MediaInfo {
+            container: None,
+            duration_ms,
+            size: std::fs::metadata(media)?.len(),
+            streams: vec![MediaStream {
+                index: 0,
+                kind: StreamKind::Audio,
+                codec: None,
+                duration_ms,
+                sample_rate: Some(16000),
+                channels: Some(1),
+                width: None,
+                height: None,
+            }],
+        })
+    }
+
+    /// Uploads are not read, so a test can send any bytes, the audio is silence as long as
+    /// the script
+    fn decode(&self, _media: &Path) -> Result<Vec<f32>> {
+        let duration_ms = self.script.duration_ms().unwrap_or(0);
+        Ok(vec![0.0; ((duration_ms * (WHISPER_SAMPLE_RATE as u64)) / 1000) as usize])
+    }
+
+    fn transcribe(
+        &self,
+        model: &str,
+        _pcm: &[f32],
+        options: &TranscriptionOptions,
+        run: &TranscribeRun
+    ) -> Result<TranscriptionResponse> {
+        let total = self.script.segments.len().max(1);
+        let mut segments = Vec::new();
+
+        for (i, scripted) in self.script.segments.iter().enumerate() {
+            if run.cancelled.load(Ordering::SeqCst) {
+                return Err(anyhow!("Cancelled"));
+            }
+
+            let speaker = options.diarize.then(|| {
+                scripted.speaker.clone().unwrap_or_else(|| format!("SPEAKER_{}", (i % 2) + 1))
+            });
+            let segment = TranscriptionSegment {
+                id: i as u32,
+                start_ms: scripted.start_ms,
+                end_ms: scripted.end_ms,
+                text: scripted.text.clone(),
+                words: mock_words(scripted),
+                no_speech_prob: 0.0,
+                avg_logprob: 0.0,
+                speaker,
+            };
+
+            (run.on_segment)(segment.clone());
+            segments.push(segment);
+            (run.on_progress)(((i + 1) as f32) / (total as f32));
+        }
+
+        let text = segments
+            .iter()
+            .map(|s| s.text.trim())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Ok(TranscriptionResponse {
+            text,
+            language: options.language.clone().unwrap_or_else(|| self.script.language.clone()),
+            segments,
+            model: Some(model.to_string()),
+            options: Some(options.clone()),
+            waveform: None,
+            thumbnail: None,
+        })
+    }
+}
+
+/// Split a segment into words spread evenly over its span
+fn mock_words(segment: &MockSegment) -> Vec<TranscriptionWord> {
+    let words: Vec<&str> = segment.text.split_whitespace().collect();
+    let span = segment.end_ms.saturating_sub(segment.start_ms);
+    let count = words.len().max(1) as u64;
+
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| TranscriptionWord {
+            text: word.to_string(),
+            start_ms: segment.start_ms + (span * (i as u64)) / count,
+            end_ms: segment.start_ms + (span * ((i as u64) + 1)) / count,
+            probability: 1.0,
+        })
+        .collect()
+}
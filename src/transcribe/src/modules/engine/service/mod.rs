@@ -0,0 +1,7 @@
+pub mod mock_transcriber;
+pub mod transcriber;
+pub mod whisper_transcriber;
+
+pub use mock_transcriber::*;
+pub use transcriber::*;
+pub use whisper_transcriber::*;
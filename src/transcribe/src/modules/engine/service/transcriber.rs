@@ -0,0 +1,43 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::modules::{
+    audio::MediaInfo,
+    model::ModelInfo,
+    whisper::{ TranscribeRun, TranscriptionOptions, TranscriptionResponse },
+};
+
+/// Something that turns uploaded media into a transcript
+///
+/// The HTTP service and the `transcribe` command only go through this trait, so they run the
+/// same with whisper or with the mock engine. Every method may block, callers keep them off
+/// the async runtime.
+pub trait Transcriber: Send + Sync + 'static {
+    /// Model used when a job does not ask for one
+    fn default_model(&self) -> &str;
+
+    /// Models a job may ask for
+    fn list_models(&self) -> Vec<ModelInfo>;
+
+    /// Validate a requested model, falling back to the default one
+    fn resolve_model(&self, name: Option<&str>) -> Result<String, String>;
+
+    /// Get a model ready, so the first job using it does not have to wait
+    fn load_model(&self, name: &str) -> Result<()>;
+
+    /// Container, duration and streams of a media file, without decoding it
+    fn probe(&self, media: &Path) -> Result<MediaInfo>;
+
+    /// Turn a media file into the mono 16kHz samples given to `transcribe`
+    fn decode(&self, media: &Path) -> Result<Vec<f32>>;
+
+    /// Transcribe decoded samples, reporting progress and stopping on cancellation through `run`
+    fn transcribe(
+        &self,
+        model: &str,
+        pcm: &[f32],
+        options: &TranscriptionOptions,
+        run: &TranscribeRun
+    ) -> Result<TranscriptionResponse>;
+}
@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::modules::{
+    audio::{ self, MediaInfo },
+    config::Config,
+    model::{ ModelInfo, ModelRegistry },
+    whisper::{ self, TranscribeRun, TranscriptionOptions, TranscriptionResponse },
+};
+
+use super::Transcriber;
+
+/// whisper.cpp, skipping silence and splitting long recordings into windows first
+pub struct WhisperTranscriber {
+    registry: ModelRegistry,
+    ffmpeg_fallback: bool,
+    vad: bool,
+    max_window_secs: u64,
+}
+
+impl WhisperTranscriber {
+    pub fn new(config: &Config) -> Self {
+        WhisperTranscriber {
+            registry: ModelRegistry::new(&config.models_dir, &config.default_model),
+            ffmpeg_fallback: config.ffmpeg_fallback,
+            vad: config.vad,
+            max_window_secs: config.max_window_secs,
+        }
+    }
+}
+
+impl Transcriber for WhisperTranscriber {
+    fn default_model(&self) -> &str {
+        self.registry.default_model()
+    }
+
+    fn list_models(&self) -> Vec<ModelInfo> {
+        self.registry.list()
+    }
+
+    fn resolve_model(&self, name: Option<&str>) -> Result<String, String> {
+        self.registry.resolve(name)
+    }
+
+    fn load_model(&self, name: &str) -> Result<()> {
+        self.registry.context(name).map(|_| ())
+    }
+
+    fn probe(&self, media: &Path) -> Result<MediaInfo> {
+        audio::probe_media(media, self.ffmpeg_fallback)
+    }
+
+    fn decode(&self, media: &Path) -> Result<Vec<f32>> {
+        audio::decode_media(media, self.ffmpeg_fallback)
+    }
+
+    fn transcribe(
+        &self,
+        model: &str,
+        pcm: &[f32],
+        options: &TranscriptionOptions,
+        run: &TranscribeRun
+    ) -> Result<TranscriptionResponse> {
+        let ctx = self.registry.context(model)?;
+        let windows = audio::speech_windows(pcm, self.vad, self.max_window_secs);
+
+        whisper::whisper_transcribe(&ctx, pcm, &windows, options, run)
+    }
+}
@@ -0,0 +1,115 @@
+use axum::{
+    extract::multipart::MultipartError,
+    extract::rejection::JsonRejection,
+    http::{ header, StatusCode },
+    response::{ IntoResponse, Response },
+    Json,
+};
+
+use super::ErrorResponse;
+
+/// Seconds a client is told to wait before retrying a request the service was too busy for
+pub const RETRY_AFTER_SECS: u64 = 30;
+
+/// Error returned by the HTTP handlers, each variant maps to one status code
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(String),
+    Unauthorized(String),
+    NotFound(String),
+    Conflict(String),
+    PayloadTooLarge(String),
+    /// The upload is not a media file the service can read
+    UnsupportedMedia(String),
+    /// The upload does not match the checksum it was declared with
+    ChecksumMismatch(String),
+    /// The job ended without a transcript, failed or cancelled
+    JobFailed(String),
+    /// Too busy to take the request now, answered with `Retry-After`
+    Unavailable(String),
+    Internal(String),
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::UnsupportedMedia(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ApiError::ChecksumMismatch(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::JobFailed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::BadRequest(_) => "bad_request",
+            ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::NotFound(_) => "not_found",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::PayloadTooLarge(_) => "payload_too_large",
+            ApiError::UnsupportedMedia(_) => "unsupported_media",
+            ApiError::ChecksumMismatch(_) => "checksum_mismatch",
+            ApiError::JobFailed(_) => "job_failed",
+            ApiError::Unavailable(_) => "unavailable",
+            ApiError::Internal(_) => "internal",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            | ApiError::BadRequest(message)
+            | ApiError::Unauthorized(message)
+            | ApiError::NotFound(message)
+            | ApiError::Conflict(message)
+            | ApiError::PayloadTooLarge(message)
+            | ApiError::UnsupportedMedia(message)
+            | ApiError::ChecksumMismatch(message)
+            | ApiError::JobFailed(message)
+            | ApiError::Unavailable(message)
+            | ApiError::Internal(message) => message,
+        }
+    }
+
+    pub fn body(&self) -> ErrorResponse {
+        ErrorResponse {
+            error: self.code().to_string(),
+            message: self.message().to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        if let ApiError::Internal(message) = &self {
+            eprintln!("Internal error: {}", message);
+        }
+
+        let mut response = (self.status(), Json(self.body())).into_response();
+        if let ApiError::Unavailable(_) = &self {
+            response.headers_mut().insert(header::RETRY_AFTER, RETRY_AFTER_SECS.into());
+        }
+        response
+    }
+}
+
+impl From<MultipartError> for ApiError {
+    fn from(e: MultipartError) -> Self {
+        match e.status() {
+            StatusCode::PAYLOAD_TOO_LARGE => ApiError::PayloadTooLarge(e.body_text()),
+            status if status.is_client_error() => ApiError::BadRequest(e.body_text()),
+            _ => ApiError::Internal(e.body_text()),
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(e: JsonRejection) -> Self {
+        ApiError::BadRequest(e.body_text())
+    }
+}
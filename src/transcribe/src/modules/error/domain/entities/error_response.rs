@@ -0,0 +1,8 @@
+use serde::{ Deserialize, Serialize };
+
+/// Body of every error reply, `error` is a stable code and `message` is meant for people
+#[derive(Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
+    pub message: String,
+}
@@ -0,0 +1,5 @@
+pub mod api_error;
+pub mod error_response;
+
+pub use api_error::*;
+pub use error_response::*;
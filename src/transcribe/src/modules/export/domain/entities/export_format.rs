@@ -0,0 +1,33 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// Formats a transcript can be downloaded in
+#[derive(Deserialize, ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Srt,
+    Vtt,
+    Txt,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Srt => "application/x-subrip; charset=utf-8",
+            ExportFormat::Vtt => "text/vtt; charset=utf-8",
+            ExportFormat::Txt => "text/plain; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Srt => "srt",
+            ExportFormat::Vtt => "vtt",
+            ExportFormat::Txt => "txt",
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+use super::ExportFormat;
+
+/// Query string of `/result/{job_id}`, JSON when no format is given
+#[derive(Deserialize, Debug, Default)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// First segment of a JSON page
+    #[serde(default)]
+    pub offset: usize,
+    /// Segments in a JSON page, the whole transcript comes at once when it is left out
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
@@ -0,0 +1,5 @@
+pub mod export_format;
+pub mod export_query;
+
+pub use export_format::*;
+pub use export_query::*;
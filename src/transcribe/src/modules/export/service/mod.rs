@@ -0,0 +1,3 @@
+pub mod render_transcript;
+
+pub use render_transcript::*;
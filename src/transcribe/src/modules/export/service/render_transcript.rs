@@ -0,0 +1,41 @@
+use anyhow::Result;
+use captions::{ render_srt, render_txt, render_vtt, CaptionSegment, CaptionWord };
+
+use crate::modules::{
+    export::domain::ExportFormat,
+    whisper::{ TranscriptionResponse, TranscriptionSegment },
+};
+
+/// Render a transcript as a downloadable document
+///
+/// Subtitles and text come from the `captions` crate, which the canister renders with too.
+pub fn render_transcript(transcript: &TranscriptionResponse, format: ExportFormat) -> Result<String> {
+    let segments = || caption_segments(&transcript.segments);
+
+    Ok(match format {
+        ExportFormat::Json => serde_json::to_string_pretty(transcript)?,
+        ExportFormat::Srt => render_srt(&segments()),
+        ExportFormat::Vtt => render_vtt(&segments()),
+        ExportFormat::Txt => render_txt(&segments()),
+    })
+}
+
+fn caption_segments(segments: &[TranscriptionSegment]) -> Vec<CaptionSegment> {
+    segments
+        .iter()
+        .map(|segment| CaptionSegment {
+            start_ms: segment.start_ms,
+            end_ms: segment.end_ms,
+            text: segment.text.clone(),
+            speaker: segment.speaker.clone(),
+            words: segment.words
+                .iter()
+                .map(|w| CaptionWord {
+                    text: w.text.clone(),
+                    start_ms: w.start_ms,
+                    end_ms: w.end_ms,
+                })
+                .collect(),
+        })
+        .collect()
+}
@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+/// Body of `POST /sessions`, declaring an upload before its chunks are sent
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateSessionRequest {
+    pub session_id: String,
+    pub total_chunks: usize,
+    pub total_size: u64,
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
@@ -0,0 +1,23 @@
+use serde::{ Deserialize, Serialize };
+
+/// Where to report a job once it finishes, instead of being polled for it
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JobCallback {
+    pub url: String,
+    /// Key the payload is signed with, so the receiver can tell it came from this service
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+impl JobCallback {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.url.starts_with("http://") && !self.url.starts_with("https://") {
+            return Err(format!("Invalid callback_url: {}", self.url));
+        }
+        if self.secret.as_deref() == Some("") {
+            return Err("callback_secret must not be empty".to_string());
+        }
+
+        Ok(())
+    }
+}
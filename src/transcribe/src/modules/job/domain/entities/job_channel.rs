@@ -0,0 +1,19 @@
+use tokio::sync::broadcast;
+
+use super::JobEvent;
+use crate::modules::whisper::TranscriptionSegment;
+
+/// Listeners of a running job, with the segments decoded so far
+///
+/// A listener joining late, or falling too far behind to see every event, gets the
+/// segments from here instead of the ones it missed.
+pub struct JobChannel {
+    pub sender: broadcast::Sender<JobEvent>,
+    pub segments: Vec<TranscriptionSegment>,
+}
+
+impl JobChannel {
+    pub fn new(capacity: usize) -> Self {
+        JobChannel { sender: broadcast::channel(capacity).0, segments: vec![] }
+    }
+}
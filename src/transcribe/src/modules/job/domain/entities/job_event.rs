@@ -0,0 +1,13 @@
+use super::JobStatus;
+use crate::modules::whisper::TranscriptionSegment;
+
+/// Something that happened to a running job, pushed to the subscribers of `/events/{job_id}`
+#[derive(Clone)]
+pub enum JobEvent {
+    Status(JobStatus),
+    /// Decoded by whisper, before the windows are stitched together and speakers assigned
+    Segment(TranscriptionSegment),
+    /// Every segment decoded so far, sent to a listener that joins late or falls behind
+    /// instead of the `Segment` events it missed
+    Segments(Vec<TranscriptionSegment>),
+}
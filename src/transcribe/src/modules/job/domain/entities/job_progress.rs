@@ -0,0 +1,36 @@
+use serde::{ Deserialize, Serialize };
+
+use super::JobStage;
+
+/// Where a running job stands
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JobProgress {
+    pub stage: JobStage,
+    /// Progress of the whole job, from 0 to 100
+    pub percent: u8,
+    /// 1 for the next job to start, only set while queued
+    #[serde(default)]
+    pub queue_position: Option<u64>,
+    /// Estimated seconds left, only known once whisper has made some progress
+    #[serde(default)]
+    pub eta_secs: Option<u64>,
+}
+
+impl JobProgress {
+    pub fn new(stage: JobStage) -> Self {
+        JobProgress {
+            stage,
+            percent: stage.percent_range().0,
+            queue_position: None,
+            eta_secs: None,
+        }
+    }
+
+    /// Record how far the current stage is, `fraction` going from 0.0 to 1.0
+    pub fn set_stage_fraction(&mut self, fraction: f32, eta_secs: Option<u64>) {
+        let (start, end) = self.stage.percent_range();
+        let fraction = fraction.clamp(0.0, 1.0);
+        self.percent = start + ((end - start) as f32 * fraction).round() as u8;
+        self.eta_secs = eta_secs;
+    }
+}
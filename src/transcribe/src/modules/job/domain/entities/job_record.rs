@@ -0,0 +1,53 @@
+use serde::{ Deserialize, Serialize };
+
+use super::{ JobCallback, JobProgress, JobStage, JobStatus };
+use crate::modules::{ job::service::unix_now, whisper::TranscriptionOptions };
+
+/// A job as it is written to the journal, so it survives a restart
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JobRecord {
+    pub job_id: String,
+    pub session_id: String,
+    /// Model the job runs with, `None` for records written before models were selectable
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub options: TranscriptionOptions,
+    #[serde(default)]
+    pub callback: Option<JobCallback>,
+    pub status: JobStatus,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+impl JobRecord {
+    pub fn new(
+        job_id: String,
+        session_id: String,
+        model: String,
+        options: TranscriptionOptions,
+        callback: Option<JobCallback>
+    ) -> Self {
+        let now = unix_now();
+        JobRecord {
+            job_id,
+            session_id,
+            model: Some(model),
+            options,
+            callback,
+            status: JobStatus::InProgress(JobProgress::new(JobStage::Queued)),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// When a finished job is dropped, running jobs never expire
+    pub fn expires_at(&self, ttl_secs: u64) -> Option<u64> {
+        self.status.is_finished().then(|| self.updated_at + ttl_secs)
+    }
+
+    pub fn set_status(&mut self, status: JobStatus) {
+        self.status = status;
+        self.updated_at = unix_now();
+    }
+}
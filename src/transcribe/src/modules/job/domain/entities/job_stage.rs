@@ -0,0 +1,31 @@
+use serde::{ Deserialize, Serialize };
+
+/// Steps a job goes through, in order
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobStage {
+    /// Waiting for a free worker
+    Queued,
+    /// Joining the uploaded chunks
+    Assembling,
+    /// Turning the media into 16kHz samples
+    Decoding,
+    /// Running whisper
+    Transcribing,
+    /// Stitching windows, diarization and saving the result
+    PostProcessing,
+}
+
+impl JobStage {
+    /// Share of the whole job covered by this stage, in percent
+    ///
+    /// Transcribing takes nearly all of the time, the other stages are quick.
+    pub fn percent_range(&self) -> (u8, u8) {
+        match self {
+            JobStage::Queued => (0, 0),
+            JobStage::Assembling => (0, 2),
+            JobStage::Decoding => (2, 10),
+            JobStage::Transcribing => (10, 95),
+            JobStage::PostProcessing => (95, 100),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use serde::Serialize;
+
+use super::{ JobProgress, JobStatus };
+
+/// Status of a job without its transcript, as `/status/{job_id}` reports it
+///
+/// Transcripts of long recordings are large, they only come from `/result/{job_id}`.
+#[derive(Serialize, Clone)]
+#[serde(tag = "status", content = "data")]
+pub enum JobState {
+    Pending,
+    InProgress(JobProgress),
+    Completed,
+    Failed(String),
+    Cancelled,
+}
+
+impl From<JobStatus> for JobState {
+    fn from(status: JobStatus) -> Self {
+        match status {
+            JobStatus::Pending => JobState::Pending,
+            JobStatus::InProgress(progress) => JobState::InProgress(progress),
+            JobStatus::Completed(_) => JobState::Completed,
+            JobStatus::Failed(e) => JobState::Failed(e),
+            JobStatus::Cancelled => JobState::Cancelled,
+        }
+    }
+}
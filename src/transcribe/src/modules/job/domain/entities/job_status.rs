@@ -0,0 +1,39 @@
+use serde::{ de::Error, Deserialize, Deserializer, Serialize };
+
+use super::JobProgress;
+use crate::modules::whisper::TranscriptionResponse;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "status", content = "data")]
+pub enum JobStatus {
+    /// Accepted but not picked up yet, also how records written before progress reporting look
+    Pending,
+    InProgress(JobProgress),
+    Completed(#[serde(deserialize_with = "deserialize_result")] Box<TranscriptionResponse>),
+    Failed(String),
+    /// Stopped on request, its audio and partial output are gone
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobStatus::Completed(_) | JobStatus::Failed(_) | JobStatus::Cancelled)
+    }
+}
+
+/// Journals written before results were typed hold the transcript as a JSON string
+fn deserialize_result<'de, D>(deserializer: D) -> Result<Box<TranscriptionResponse>, D::Error>
+    where D: Deserializer<'de>
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StoredResult {
+        Typed(Box<TranscriptionResponse>),
+        Json(String),
+    }
+
+    match StoredResult::deserialize(deserializer)? {
+        StoredResult::Typed(result) => Ok(result),
+        StoredResult::Json(json) => serde_json::from_str(&json).map_err(D::Error::custom),
+    }
+}
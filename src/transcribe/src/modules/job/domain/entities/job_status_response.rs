@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+use super::JobState;
+
+/// Reply of `/status/{job_id}`
+#[derive(Serialize)]
+pub struct JobStatusResponse {
+    #[serde(flatten)]
+    pub status: JobState,
+    /// Unix time after which a finished job and its result are deleted
+    pub expires_at: Option<u64>,
+}
@@ -0,0 +1,29 @@
+pub mod create_session_request;
+pub mod job_callback;
+pub mod job_channel;
+pub mod job_event;
+pub mod job_progress;
+pub mod job_record;
+pub mod job_stage;
+pub mod job_state;
+pub mod job_status;
+pub mod job_status_response;
+pub mod probe_request;
+pub mod session_record;
+pub mod session_status_response;
+pub mod submit_error;
+
+pub use create_session_request::*;
+pub use job_callback::*;
+pub use job_channel::*;
+pub use job_event::*;
+pub use job_progress::*;
+pub use job_record::*;
+pub use job_stage::*;
+pub use job_state::*;
+pub use job_status::*;
+pub use job_status_response::*;
+pub use probe_request::*;
+pub use session_record::*;
+pub use session_status_response::*;
+pub use submit_error::*;
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+/// Body of `POST /probe`, naming a fully uploaded session
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProbeRequest {
+    pub session_id: String,
+}
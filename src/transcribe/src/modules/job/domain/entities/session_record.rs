@@ -0,0 +1,33 @@
+use serde::{ Deserialize, Serialize };
+
+/// Metadata of an upload session kept next to its chunk files
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct SessionRecord {
+    pub session_id: String,
+    /// What the client said it will send, `None` for sessions opened before uploads were declared
+    #[serde(default)]
+    pub total_chunks: Option<usize>,
+    #[serde(default)]
+    pub total_size: Option<u64>,
+    /// Hex SHA-256 of the whole upload, checked before the job starts
+    #[serde(default)]
+    pub sha256: Option<String>,
+    pub received_chunks: Vec<usize>,
+    pub created_at: u64,
+    /// When the last chunk arrived, 0 for records written before it was tracked
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+impl SessionRecord {
+    pub fn last_activity(&self) -> u64 {
+        self.created_at.max(self.updated_at)
+    }
+
+    /// Declared chunks that have not arrived yet
+    pub fn missing_chunks(&self) -> Vec<usize> {
+        (0..self.total_chunks.unwrap_or(0))
+            .filter(|i| !self.received_chunks.contains(i))
+            .collect()
+    }
+}
@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+/// Reply of `GET /sessions/{session_id}`, tells a client which chunks to send again
+#[derive(Serialize)]
+pub struct SessionStatusResponse {
+    pub session_id: String,
+    pub total_chunks: Option<usize>,
+    pub total_size: Option<u64>,
+    pub received_size: u64,
+    pub received_chunks: Vec<usize>,
+    pub missing_chunks: Vec<usize>,
+}
@@ -0,0 +1,18 @@
+use std::fmt;
+
+/// Why a job could not be handed to the worker pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitError {
+    /// Every worker is busy and the queue is at its limit
+    QueueFull,
+    ShutDown,
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::QueueFull => write!(f, "Job queue is full, try again later"),
+            SubmitError::ShutDown => write!(f, "Worker pool is shut down"),
+        }
+    }
+}
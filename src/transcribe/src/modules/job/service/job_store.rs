@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+use std::fs::{ self, File };
+use std::io::{ self, BufWriter, Write };
+use std::path::{ Path, PathBuf };
+use std::sync::{ Arc, Mutex };
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+use serde::{ de::DeserializeOwned, Serialize };
+use sha2::{ Digest, Sha256 };
+use tempfile::NamedTempFile;
+
+use crate::modules::job::domain::{ JobRecord, SessionRecord };
+
+const JOBS_DIR: &str = "jobs";
+const SESSIONS_DIR: &str = "sessions";
+const SESSION_FILE: &str = "session.json";
+const MEDIA_FILE: &str = "media";
+
+/// Journal directory holding jobs and upload sessions on disk
+///
+/// Layout:
+/// - `jobs/<job_id>.json`
+/// - `sessions/<session_id>/session.json`
+/// - `sessions/<session_id>/<chunk_index>.chunk`
+/// - `sessions/<session_id>/media`, the chunks put together once the job starts
+///
+/// Files are written under a unique temporary name and renamed into place, and changes to a
+/// session's metadata are serialized, so concurrent chunk uploads never lose each other.
+pub struct JobStore {
+    root: PathBuf,
+    session_locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+impl JobStore {
+    pub fn open(root: impl Into<PathBuf>) -> io::Result<Self> {
+        let root = root.into();
+        fs::create_dir_all(root.join(JOBS_DIR))?;
+        fs::create_dir_all(root.join(SESSIONS_DIR))?;
+        Ok(JobStore { root, session_locks: Mutex::new(HashMap::new()) })
+    }
+
+    /* Jobs */
+
+    pub fn save_job(&self, job: &JobRecord) -> io::Result<()> {
+        let path = self.job_path(&job.job_id)?;
+        write_json(&path, job)
+    }
+
+    pub fn remove_job(&self, job_id: &str) -> io::Result<()> {
+        match fs::remove_file(self.job_path(job_id)?) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn load_jobs(&self) -> io::Result<Vec<JobRecord>> {
+        let mut jobs = Vec::new();
+
+        for entry in fs::read_dir(self.root.join(JOBS_DIR))? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+
+            match read_json::<JobRecord>(&path) {
+                Ok(job) => jobs.push(job),
+                Err(e) => eprintln!("Skipping unreadable job file {}: {}", path.display(), e),
+            }
+        }
+
+        Ok(jobs)
+    }
+
+    /* Upload sessions */
+
+    /// Journal a new session, or return the one already declared under the same id
+    pub fn create_session(&self, record: SessionRecord) -> io::Result<SessionRecord> {
+        let dir = self.session_dir(&record.session_id)?;
+        let lock = self.session_lock(&record.session_id);
+        let _guard = lock.lock().unwrap();
+
+        if let Some(existing) = self.session(&record.session_id)? {
+            return Ok(existing);
+        }
+
+        fs::create_dir_all(&dir)?;
+        write_json(&dir.join(SESSION_FILE), &record)?;
+        Ok(record)
+    }
+
+    pub fn session(&self, session_id: &str) -> io::Result<Option<SessionRecord>> {
+        match read_json::<SessionRecord>(&self.session_dir(session_id)?.join(SESSION_FILE)) {
+            Ok(record) => Ok(Some(record)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Open a spool file for a chunk, it only becomes part of the session with `commit_chunk`
+    ///
+    /// Each upload gets its own file, so a chunk sent twice at once cannot mix both bodies.
+    /// Dropping the file without committing it deletes it.
+    pub fn create_chunk(&self, session_id: &str, chunk_index: usize) -> io::Result<NamedTempFile> {
+        let dir = self.session_dir(session_id)?;
+        fs::create_dir_all(&dir)?;
+        tempfile::Builder
+            ::new()
+            .prefix(&format!("{}.chunk.", chunk_index))
+            .suffix(".tmp")
+            .tempfile_in(&dir)
+    }
+
+    /// Move a fully written chunk into place and record it in the session
+    pub fn commit_chunk(
+        &self,
+        session_id: &str,
+        chunk_index: usize,
+        chunk: NamedTempFile
+    ) -> io::Result<()> {
+        let dir = self.session_dir(session_id)?;
+        let lock = self.session_lock(session_id);
+        let _guard = lock.lock().unwrap();
+
+        // Sessions are declared before any chunk is accepted
+        let mut record = read_json::<SessionRecord>(&dir.join(SESSION_FILE))?;
+        record.updated_at = unix_now();
+
+        chunk.persist(dir.join(format!("{}.chunk", chunk_index))).map_err(|e| e.error)?;
+
+        if !record.received_chunks.contains(&chunk_index) {
+            record.received_chunks.push(chunk_index);
+            record.received_chunks.sort_unstable();
+        }
+        write_json(&dir.join(SESSION_FILE), &record)
+    }
+
+    pub fn has_session(&self, session_id: &str) -> bool {
+        self.session_dir(session_id)
+            .map(|dir| dir.join(SESSION_FILE).is_file())
+            .unwrap_or(false)
+    }
+
+    /// Bytes received for a session so far, leaving out a chunk that is about to be replaced
+    pub fn session_size(
+        &self,
+        record: &SessionRecord,
+        except_chunk: Option<usize>
+    ) -> io::Result<u64> {
+        let dir = self.session_dir(&record.session_id)?;
+
+        let mut size = 0;
+        for index in record.received_chunks.iter().filter(|i| Some(**i) != except_chunk) {
+            size += fs::metadata(dir.join(format!("{}.chunk", index)))?.len();
+        }
+
+        Ok(size)
+    }
+
+    /// Hex SHA-256 of the chunks of a session put together, read one chunk at a time
+    pub fn session_checksum(&self, record: &SessionRecord) -> io::Result<String> {
+        let dir = self.session_dir(&record.session_id)?;
+
+        let mut hasher = Sha256::new();
+        for index in &record.received_chunks {
+            let mut chunk = File::open(dir.join(format!("{}.chunk", index)))?;
+            io::copy(&mut chunk, &mut hasher)?;
+        }
+
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Concatenate the chunks of a session into one media file next to them
+    ///
+    /// Chunks are copied one at a time, so the whole upload is never held in memory.
+    pub fn assemble_session(&self, session_id: &str) -> io::Result<Option<PathBuf>> {
+        let dir = self.session_dir(session_id)?;
+        let Some(record) = self.session(session_id)? else {
+            return Ok(None);
+        };
+
+        let path = dir.join(MEDIA_FILE);
+        write_media(&dir, &record, File::create(&path)?)?;
+
+        Ok(Some(path))
+    }
+
+    /// Same as `assemble_session`, into a temporary file deleted once dropped
+    ///
+    /// Used to look at an upload before it is finalized, without touching the media file
+    /// a job may be writing at the same time.
+    pub fn assemble_session_copy(&self, session_id: &str) -> io::Result<Option<NamedTempFile>> {
+        let dir = self.session_dir(session_id)?;
+        let Some(record) = self.session(session_id)? else {
+            return Ok(None);
+        };
+
+        let copy = tempfile::Builder::new().prefix(".media").tempfile_in(&dir)?;
+        write_media(&dir, &record, copy.reopen()?)?;
+
+        Ok(Some(copy))
+    }
+
+    /// Metadata of every journaled session, used to find abandoned ones
+    pub fn list_sessions(&self) -> io::Result<Vec<SessionRecord>> {
+        let mut records = Vec::new();
+
+        for entry in fs::read_dir(self.root.join(SESSIONS_DIR))? {
+            let path = entry?.path().join(SESSION_FILE);
+            match read_json::<SessionRecord>(&path) {
+                Ok(record) => records.push(record),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => eprintln!("Skipping unreadable session {}: {}", path.display(), e),
+            }
+        }
+
+        Ok(records)
+    }
+
+    pub fn remove_session(&self, session_id: &str) -> io::Result<()> {
+        let dir = self.session_dir(session_id)?;
+        let lock = self.session_lock(session_id);
+        let _guard = lock.lock().unwrap();
+
+        self.session_locks.lock().unwrap().remove(session_id);
+        match fs::remove_dir_all(dir) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Lock held while a session's metadata is read and written back
+    fn session_lock(&self, session_id: &str) -> Arc<Mutex<()>> {
+        Arc::clone(self.session_locks.lock().unwrap().entry(session_id.to_string()).or_default())
+    }
+
+    /* Paths */
+
+    fn job_path(&self, job_id: &str) -> io::Result<PathBuf> {
+        Ok(self.root.join(JOBS_DIR).join(format!("{}.json", checked_id(job_id)?)))
+    }
+
+    fn session_dir(&self, session_id: &str) -> io::Result<PathBuf> {
+        Ok(self.root.join(SESSIONS_DIR).join(checked_id(session_id)?))
+    }
+}
+
+fn write_media(dir: &Path, record: &SessionRecord, file: File) -> io::Result<()> {
+    let mut media = BufWriter::new(file);
+    for index in &record.received_chunks {
+        let mut chunk = File::open(dir.join(format!("{}.chunk", index)))?;
+        io::copy(&mut chunk, &mut media)?;
+    }
+    media.flush()
+}
+
+/// Ids end up in file names, so only allow characters that cannot escape the journal directory
+pub fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() &&
+        id.len() <= 128 &&
+        id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn checked_id(id: &str) -> io::Result<&str> {
+    if is_valid_id(id) {
+        Ok(id)
+    } else {
+        Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid id: {:?}", id)))
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let bytes = fs::read(path)?;
+    serde_json::from_slice(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let bytes = serde_json::to_vec(value).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    write_bytes(path, &bytes)
+}
+
+/// Write to a temp file first and rename, so a crash never leaves a half-written file behind
+///
+/// The temp file gets a unique name, two writers of the same file never share it.
+fn write_bytes(path: &Path, data: &[u8]) -> io::Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut tmp = tempfile::Builder::new().prefix(".").suffix(".tmp").tempfile_in(dir)?;
+    tmp.write_all(data)?;
+    tmp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
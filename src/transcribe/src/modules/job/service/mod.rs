@@ -0,0 +1,5 @@
+pub mod job_store;
+pub mod worker_pool;
+
+pub use job_store::*;
+pub use worker_pool::*;
@@ -0,0 +1,65 @@
+use std::panic::{ self, AssertUnwindSafe };
+use std::sync::mpsc::{ self, Receiver, SyncSender, TrySendError };
+use std::sync::{ Arc, Mutex };
+use std::thread;
+
+use crate::modules::job::domain::SubmitError;
+
+type Task = Box<dyn FnOnce() + Send + 'static>;
+
+/// Fixed set of OS threads for CPU-heavy work, fed by a bounded queue
+///
+/// Whisper blocks for minutes at a time, so it must never run on the async runtime.
+pub struct WorkerPool {
+    sender: SyncSender<Task>,
+}
+
+impl WorkerPool {
+    pub fn new(workers: usize, queue_size: usize) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<Task>(queue_size);
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        for i in 0..workers.max(1) {
+            let receiver = Arc::clone(&receiver);
+            thread::Builder
+                ::new()
+                .name(format!("transcribe-worker-{}", i))
+                .spawn(move || run_worker(receiver))
+                .expect("Failed to spawn worker thread");
+        }
+
+        WorkerPool { sender }
+    }
+
+    /// Queue a task, failing immediately instead of blocking when the queue is full
+    pub fn submit<F>(&self, task: F) -> Result<(), SubmitError> where F: FnOnce() + Send + 'static {
+        self.sender.try_send(Box::new(task)).map_err(|e| {
+            match e {
+                TrySendError::Full(_) => SubmitError::QueueFull,
+                TrySendError::Disconnected(_) => SubmitError::ShutDown,
+            }
+        })
+    }
+}
+
+fn run_worker(receiver: Arc<Mutex<Receiver<Task>>>) {
+    loop {
+        // Only hold the lock while waiting, not while running the task
+        let task = {
+            let receiver = receiver.lock().unwrap();
+            receiver.recv()
+        };
+
+        match task {
+            Ok(task) => {
+                // Keep the worker alive if a task panics
+                if panic::catch_unwind(AssertUnwindSafe(task)).is_err() {
+                    eprintln!("Worker task panicked");
+                }
+            }
+            Err(_) => {
+                break;
+            }
+        }
+    }
+}
@@ -1,3 +1,20 @@
+pub mod audio;
+pub mod auth;
+pub mod cli;
+pub mod config;
+pub mod diarization;
+pub mod engine;
+pub mod error;
+pub mod export;
+pub mod job;
+pub mod model;
+pub mod webhook;
 pub mod whisper;
 
+pub use config::*;
+pub use engine::*;
+pub use error::*;
+pub use export::*;
+pub use job::*;
+pub use model::*;
 pub use whisper::*;
@@ -0,0 +1,5 @@
+pub mod model_info;
+pub mod models_response;
+
+pub use model_info::*;
+pub use models_response::*;
@@ -0,0 +1,14 @@
+use serde::{ Deserialize, Serialize };
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ModelInfo {
+    pub name: String,
+    pub file: String,
+    pub size_mb: u64,
+    pub quantization: Option<String>,
+    pub english_only: bool,
+    /// The model file exists in the models directory
+    pub available: bool,
+    /// The model is loaded in memory and ready to use
+    pub loaded: bool,
+}
@@ -0,0 +1,9 @@
+use serde::{ Deserialize, Serialize };
+
+use super::ModelInfo;
+
+#[derive(Serialize, Deserialize)]
+pub struct ModelsResponse {
+    pub default_model: String,
+    pub models: Vec<ModelInfo>,
+}
@@ -0,0 +1,3 @@
+pub mod model_registry;
+
+pub use model_registry::*;
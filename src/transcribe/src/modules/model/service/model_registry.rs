@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{ Arc, Mutex };
+
+use anyhow::{ anyhow, Result };
+use once_cell::sync::OnceCell;
+use whisper_rs::WhisperContext;
+
+use crate::modules::{ model::domain::ModelInfo, whisper };
+
+/// Official ggml conversions of the whisper models, with their approximate size on disk
+const KNOWN_MODELS: &[(&str, u64)] = &[
+    ("tiny", 75),
+    ("tiny.en", 75),
+    ("tiny-q5_1", 31),
+    ("tiny.en-q5_1", 31),
+    ("tiny-q8_0", 42),
+    ("base", 142),
+    ("base.en", 142),
+    ("base-q5_1", 57),
+    ("base.en-q5_1", 57),
+    ("base-q8_0", 78),
+    ("small", 466),
+    ("small.en", 466),
+    ("small-q5_1", 181),
+    ("small.en-q5_1", 181),
+    ("small-q8_0", 252),
+    ("small.en-tdrz", 465),
+    ("medium", 1500),
+    ("medium.en", 1500),
+    ("medium-q5_0", 514),
+    ("medium.en-q5_0", 514),
+    ("medium-q8_0", 785),
+    ("large-v3", 2900),
+    ("large-v3-q5_0", 1100),
+    ("large-v3-turbo", 1500),
+    ("large-v3-turbo-q5_0", 547),
+    ("large-v3-turbo-q8_0", 834),
+];
+
+/// Model context, filled by whichever job needs the model first
+type ContextSlot = Arc<OnceCell<Arc<WhisperContext>>>;
+
+/// Models available in the models directory, loaded on first use and then shared by every job
+pub struct ModelRegistry {
+    models_dir: PathBuf,
+    default_model: String,
+    /// Only held to look up a slot, loading happens outside of it
+    contexts: Mutex<HashMap<String, ContextSlot>>,
+}
+
+impl ModelRegistry {
+    pub fn new(models_dir: impl Into<PathBuf>, default_model: impl Into<String>) -> Self {
+        ModelRegistry {
+            models_dir: models_dir.into(),
+            default_model: default_model.into(),
+            contexts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn default_model(&self) -> &str {
+        &self.default_model
+    }
+
+    /// Known models plus any other `ggml-*.bin` file found in the models directory
+    pub fn list(&self) -> Vec<ModelInfo> {
+        let loaded: Vec<String> = self.contexts
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, slot)| slot.get().is_some())
+            .map(|(name, _)| name.clone())
+            .collect();
+        let is_loaded = |name: &str| loaded.iter().any(|loaded| loaded == name);
+
+        let mut models: Vec<ModelInfo> = KNOWN_MODELS.iter()
+            .map(|(name, size_mb)| {
+                let file = model_file(name);
+                let available = self.models_dir.join(&file).is_file();
+                model_info(name, file, *size_mb, available, is_loaded(name))
+            })
+            .collect();
+
+        if let Ok(entries) = fs::read_dir(&self.models_dir) {
+            for entry in entries.flatten() {
+                let file = entry.file_name().to_string_lossy().to_string();
+                let Some(name) = file.strip_prefix("ggml-").and_then(|f| f.strip_suffix(".bin")) else {
+                    continue;
+                };
+                if KNOWN_MODELS.iter().any(|(known, _)| *known == name) {
+                    continue;
+                }
+
+                let size_mb = entry
+                    .metadata()
+                    .map(|m| m.len() / (1024 * 1024))
+                    .unwrap_or(0);
+                models.push(model_info(name, file.clone(), size_mb, true, is_loaded(name)));
+            }
+        }
+
+        models
+    }
+
+    /// Validate a requested model, falling back to the default one
+    pub fn resolve(&self, name: Option<&str>) -> Result<String, String> {
+        let name = name.unwrap_or(&self.default_model);
+
+        match self.list().into_iter().find(|m| m.name == name) {
+            Some(model) if model.available => Ok(model.name),
+            Some(model) => Err(format!("Model {} is not installed on this server", model.name)),
+            None => Err(format!("Unknown model: {}", name)),
+        }
+    }
+
+    /// Get the context of a model, loading it from disk the first time
+    ///
+    /// Jobs asking for the same model wait for a single load, other models are not held up.
+    pub fn context(&self, name: &str) -> Result<Arc<WhisperContext>> {
+        let loaded = self.contexts
+            .lock()
+            .unwrap()
+            .get(name)
+            .and_then(|slot| slot.get().cloned());
+        if let Some(ctx) = loaded {
+            return Ok(ctx);
+        }
+
+        let name = self.resolve(Some(name)).map_err(|e| anyhow!(e))?;
+        let slot = Arc::clone(self.contexts.lock().unwrap().entry(name.clone()).or_default());
+
+        let ctx = slot.get_or_try_init(|| {
+            let path = self.models_dir.join(model_file(&name));
+            whisper::load_model(&path.to_string_lossy()).map(Arc::new)
+        })?;
+
+        Ok(Arc::clone(ctx))
+    }
+}
+
+fn model_file(name: &str) -> String {
+    format!("ggml-{}.bin", name)
+}
+
+fn model_info(name: &str, file: String, size_mb: u64, available: bool, loaded: bool) -> ModelInfo {
+    let (base, quantization) = match name.rsplit_once('-') {
+        Some((base, suffix)) if suffix.starts_with('q') => (base, Some(suffix.to_string())),
+        _ => (name, None),
+    };
+
+    ModelInfo {
+        name: name.to_string(),
+        file,
+        size_mb,
+        quantization,
+        english_only: base.ends_with(".en"),
+        available,
+        loaded,
+    }
+}
@@ -0,0 +1,3 @@
+pub mod webhook_payload;
+
+pub use webhook_payload::*;
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+use crate::modules::job::JobStatus;
+
+/// Body POSTed to a job's callback URL, the final status in the same shape as `/status`
+#[derive(Serialize)]
+pub struct WebhookPayload<'a> {
+    pub job_id: &'a str,
+    #[serde(flatten)]
+    pub status: &'a JobStatus,
+}
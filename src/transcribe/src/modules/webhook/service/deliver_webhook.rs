@@ -0,0 +1,165 @@
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{ anyhow, Result };
+use axum::http::Uri;
+use sha2::{ Digest, Sha256 };
+
+use crate::modules::auth::{
+    sign_payload,
+    signed_message,
+    CONTENT_SHA256_HEADER,
+    NONCE_HEADER,
+    SIGNATURE_HEADER,
+    TIMESTAMP_HEADER,
+};
+use crate::modules::job::{ unix_now, JobCallback };
+
+const MAX_ATTEMPTS: u32 = 6;
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(2);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// POST a finished job to its callback URL on a thread of its own
+///
+/// Failed deliveries are retried with exponential backoff, 2s then 4s, 8s, ... until
+/// `MAX_ATTEMPTS` is reached. The receiver should answer with any 2xx status.
+pub fn deliver_webhook(job_id: String, callback: JobCallback, body: Vec<u8>) {
+    thread::spawn(move || {
+        // Signed once, so every attempt carries the same nonce and a retry can be told apart
+        // from a new delivery
+        let headers = match signature_headers(&callback, &body, unix_now()) {
+            Ok(headers) => headers,
+            Err(e) => {
+                eprintln!("Failed to sign webhook for job {}: {}", job_id, e);
+                return;
+            }
+        };
+        let mut delay = FIRST_RETRY_DELAY;
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            match post_callback(&callback, &headers, &body) {
+                Ok(()) => {
+                    println!("Delivered job {} to {}", job_id, callback.url);
+                    return;
+                }
+                Err(e) if attempt < MAX_ATTEMPTS => {
+                    eprintln!(
+                        "Webhook for job {} failed (attempt {}/{}), retrying in {}s: {}",
+                        job_id,
+                        attempt,
+                        MAX_ATTEMPTS,
+                        delay.as_secs(),
+                        e
+                    );
+                    thread::sleep(delay);
+                    delay *= 2;
+                }
+                Err(e) => {
+                    eprintln!("Giving up on webhook for job {}: {}", job_id, e);
+                }
+            }
+        }
+    });
+}
+
+/// Headers signing a delivery the way the service's own requests are signed, with the
+/// callback secret as the key
+///
+/// The signature is the hex HMAC-SHA256 of
+/// `<timestamp>\n<nonce>\nPOST\n<path?query of the callback URL>\n<hex SHA-256 of body>`, see
+/// `auth::signed_message`. Without a secret the delivery is sent unsigned.
+fn signature_headers(
+    callback: &JobCallback,
+    body: &[u8],
+    timestamp: u64
+) -> Result<Vec<(&'static str, String)>> {
+    let Some(secret) = &callback.secret else {
+        return Ok(vec![]);
+    };
+
+    let url: Uri = callback.url
+        .parse()
+        .map_err(|e| anyhow!("Invalid callback URL {}: {}", callback.url, e))?;
+    let path = url
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/");
+    let nonce = uuid::Uuid::new_v4().to_string();
+    let body_sha256 = hex::encode(Sha256::digest(body));
+    let message = signed_message(timestamp, &nonce, "POST", path, &body_sha256);
+
+    Ok(
+        vec![
+            (TIMESTAMP_HEADER, timestamp.to_string()),
+            (NONCE_HEADER, nonce),
+            (CONTENT_SHA256_HEADER, body_sha256),
+            (SIGNATURE_HEADER, sign_payload(secret, &message))
+        ]
+    )
+}
+
+fn post_callback(
+    callback: &JobCallback,
+    headers: &[(&'static str, String)],
+    body: &[u8]
+) -> Result<()> {
+    let client = reqwest::blocking::Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+
+    let mut request = client
+        .post(&callback.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body.to_vec());
+    for (name, value) in headers {
+        request = request.header(*name, value.clone());
+    }
+
+    let response = request.send()?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Callback answered with status {}", response.status()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::auth::verify_payload;
+
+    #[test]
+    fn signs_like_requests_are_signed() {
+        let callback = JobCallback {
+            url: "https://example.com/hooks/transcribe?user=1".to_string(),
+            secret: Some("callback-secret".to_string()),
+        };
+        let body = br#"{"job_id":"job-1","status":"Cancelled"}"#;
+
+        let headers = signature_headers(&callback, body, 1_700_000_000).unwrap();
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(header, _)| *header == name)
+                .map(|(_, value)| value.as_str())
+                .unwrap()
+        };
+
+        let body_sha256 = hex::encode(Sha256::digest(body));
+        assert_eq!(header(TIMESTAMP_HEADER), "1700000000");
+        assert_eq!(header(CONTENT_SHA256_HEADER), body_sha256);
+
+        let message = format!(
+            "1700000000\n{}\nPOST\n/hooks/transcribe?user=1\n{}",
+            header(NONCE_HEADER),
+            body_sha256
+        );
+        let signature = hex::decode(header(SIGNATURE_HEADER)).unwrap();
+        assert!(verify_payload("callback-secret", message.as_bytes(), &signature));
+    }
+
+    #[test]
+    fn unsigned_without_a_secret() {
+        let callback = JobCallback { url: "https://example.com/hook".to_string(), secret: None };
+
+        assert!(signature_headers(&callback, b"{}", 1_700_000_000).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod deliver_webhook;
+
+pub use deliver_webhook::*;
@@ -1,7 +1,13 @@
+pub mod transcription_options;
+pub mod transcription_page;
 pub mod transcription_response;
 pub mod upload_response;
 pub mod transcription_segment;
+pub mod transcription_word;
 
+pub use transcription_options::*;
+pub use transcription_page::*;
 pub use transcription_response::*;
 pub use upload_response::*;
 pub use transcription_segment::*;
+pub use transcription_word::*;
@@ -0,0 +1,86 @@
+use serde::{ Deserialize, Serialize };
+
+const MAX_BEAM_SIZE: u32 = 16;
+const MAX_BEST_OF: u32 = 16;
+const MAX_SPEAKERS: u32 = 32;
+
+/// Decoding settings of a job, every field falls back to whisper's usual behaviour
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TranscriptionOptions {
+    /// ISO 639-1 code of the spoken language, detected from the audio when missing
+    pub language: Option<String>,
+    /// Translate the speech to English instead of transcribing it
+    pub translate: bool,
+    /// Text fed to the decoder before the audio, used for custom vocabulary and spelling
+    pub initial_prompt: Option<String>,
+    /// Decode with beam search of this width, greedy sampling is used when missing
+    pub beam_size: Option<u32>,
+    /// Candidates sampled per step when decoding greedily
+    pub best_of: u32,
+    pub temperature: f32,
+    /// Temperature added when a decode fails whisper's quality checks, 0 disables the fallback
+    pub temperature_increment: f32,
+    /// Label the speaker of every segment
+    pub diarize: bool,
+    /// Upper bound on the number of speakers told apart, 8 when missing
+    pub max_speakers: Option<u32>,
+}
+
+impl Default for TranscriptionOptions {
+    fn default() -> Self {
+        TranscriptionOptions {
+            language: None,
+            translate: false,
+            initial_prompt: None,
+            beam_size: None,
+            best_of: 1,
+            temperature: 0.0,
+            temperature_increment: 0.2,
+            diarize: false,
+            max_speakers: None,
+        }
+    }
+}
+
+impl TranscriptionOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(language) = &self.language {
+            if whisper_rs::get_lang_id(language).is_none() {
+                return Err(format!("Unsupported language: {}", language));
+            }
+        }
+
+        if let Some(prompt) = &self.initial_prompt {
+            if prompt.contains('\0') {
+                return Err("Initial prompt must not contain NUL characters".to_string());
+            }
+        }
+
+        if let Some(beam_size) = self.beam_size {
+            if !(1..=MAX_BEAM_SIZE).contains(&beam_size) {
+                return Err(format!("beam_size must be between 1 and {}", MAX_BEAM_SIZE));
+            }
+        }
+
+        if !(1..=MAX_BEST_OF).contains(&self.best_of) {
+            return Err(format!("best_of must be between 1 and {}", MAX_BEST_OF));
+        }
+
+        if !(0.0..=1.0).contains(&self.temperature) {
+            return Err("temperature must be between 0.0 and 1.0".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.temperature_increment) {
+            return Err("temperature_increment must be between 0.0 and 1.0".to_string());
+        }
+
+        if let Some(max_speakers) = self.max_speakers {
+            if !(1..=MAX_SPEAKERS).contains(&max_speakers) {
+                return Err(format!("max_speakers must be between 1 and {}", MAX_SPEAKERS));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+use super::TranscriptionResponse;
+
+/// Part of a completed transcript, what `/result/{job_id}?offset=..&limit=..` returns
+///
+/// Long recordings give results too large for a single canister HTTP outcall, so they are
+/// fetched a range of segments at a time. The waveform and thumbnail only come with the
+/// first page.
+#[derive(Serialize)]
+pub struct TranscriptionPage {
+    #[serde(flatten)]
+    pub result: TranscriptionResponse,
+    /// Index of the first segment of the page in the whole transcript
+    pub offset: usize,
+    pub total_segments: usize,
+}
+
+impl TranscriptionPage {
+    pub fn new(mut result: TranscriptionResponse, offset: usize, limit: usize) -> Self {
+        let total_segments = result.segments.len();
+        result.segments = result.segments.into_iter().skip(offset).take(limit).collect();
+
+        if offset > 0 {
+            result.waveform = None;
+            result.thumbnail = None;
+        }
+
+        TranscriptionPage { result, offset, total_segments }
+    }
+}
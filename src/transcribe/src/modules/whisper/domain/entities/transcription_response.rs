@@ -1,10 +1,23 @@
 use serde::{ Deserialize, Serialize };
 
-use super::TranscriptionSegment;
+use crate::modules::audio::{ Thumbnail, Waveform };
 
-#[derive(Serialize, Deserialize)]
+use super::{ TranscriptionOptions, TranscriptionSegment };
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionResponse {
     pub text: String,
     pub language: String,
     pub segments: Vec<TranscriptionSegment>,
+    /// Model and settings that produced the transcript
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub options: Option<TranscriptionOptions>,
+    /// Outline of the audio, for drawing a scrubbable waveform
+    #[serde(default)]
+    pub waveform: Option<Waveform>,
+    /// Poster frame, only for videos and when ffmpeg is installed
+    #[serde(default)]
+    pub thumbnail: Option<Thumbnail>,
 }
@@ -1,9 +1,26 @@
 use serde::{ Deserialize, Serialize };
 
+use super::TranscriptionWord;
+
+/// A transcribed span of the media
+///
+/// Times are integer milliseconds from the start of the media, so they can be used
+/// as-is for subtitles and seeking without any float rounding.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionSegment {
     pub id: u32,
-    pub start: f32,
-    pub end: f32,
+    pub start_ms: u64,
+    pub end_ms: u64,
     pub text: String,
+    #[serde(default)]
+    pub words: Vec<TranscriptionWord>,
+    /// Probability that the segment contains no speech at all
+    #[serde(default)]
+    pub no_speech_prob: f32,
+    /// Mean log-probability of the text tokens, low values hint at a poor transcript
+    #[serde(default)]
+    pub avg_logprob: f32,
+    /// Speaker label such as `SPEAKER_1`, only set when diarization was requested
+    #[serde(default)]
+    pub speaker: Option<String>,
 }
@@ -0,0 +1,11 @@
+use serde::{ Deserialize, Serialize };
+
+/// A single word of a segment, times in integer milliseconds like the segment itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionWord {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// Lowest probability among the tokens of the word, from 0.0 to 1.0
+    pub probability: f32,
+}
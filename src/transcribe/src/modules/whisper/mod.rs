@@ -1,107 +1,431 @@
-use std::process::{ Command, Stdio };
-use std::io::{ Read, Write };
-use whisper_rs::{ WhisperContext, WhisperContextParameters, FullParams, SamplingStrategy };
-use tempfile::NamedTempFile;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::{ atomic::{ AtomicBool, AtomicUsize, Ordering }, Arc, Mutex };
+use std::thread;
+
+use whisper_rs::{
+    WhisperContext,
+    WhisperContextParameters,
+    WhisperSegment,
+    WhisperState,
+    WhisperTokenId,
+    FullParams,
+    SamplingStrategy,
+    SegmentCallbackData,
+};
 
 use anyhow::{ anyhow, Result };
-use std::io;
+
+use crate::modules::{ audio::WHISPER_SAMPLE_RATE, diarization };
 
 pub mod domain;
 
 pub use domain::*;
 
-pub fn extract_audio_from_video(video_data: &[u8]) -> io::Result<Vec<u8>> {
-    // Save video data to a temp file
-    let mut temp_video = NamedTempFile::new()?;
-    temp_video.write_all(video_data)?;
-
-    // ffmpeg command: extract mono 16kHz WAV to stdout
-    let mut cmd = Command::new("ffmpeg")
-        .args([
-            "-i",
-            temp_video.path().to_str().unwrap(),
-            "-vn",
-            "-ac",
-            "1",
-            "-ar",
-            "16000",
-            "-f",
-            "wav",
-            "pipe:1",
-        ])
-        .stdout(Stdio::piped())
-        .stderr(Stdio::null())
-        .spawn()?;
-
-    let mut buf = Vec::new();
-    if let Some(mut out) = cmd.stdout.take() {
-        out.read_to_end(&mut buf)?;
+/// Load a ggml model once, the context is shared by every job
+pub fn load_model(path_to_model: &str) -> Result<WhisperContext> {
+    WhisperContext::new_with_params(path_to_model, WhisperContextParameters::default()).map_err(|e|
+        anyhow!("Model load failed ({}): {}", path_to_model, e)
+    )
+}
+
+/// Transcribe mono 16kHz samples window by window, blocking the calling thread until whisper is done
+///
+/// `windows` are sample ranges of `pcm`, usually the speech found by the VAD. Up to `run.parallel`
+/// windows run at the same time on their own whisper state, splitting `run.n_threads` between them.
+/// Segment times are shifted back onto the timeline of the whole recording.
+pub fn whisper_transcribe(
+    ctx: &WhisperContext,
+    pcm: &[f32],
+    windows: &[Range<usize>],
+    options: &TranscriptionOptions,
+    run: &TranscribeRun
+) -> Result<TranscriptionResponse> {
+    let parallel = run.parallel.clamp(1, windows.len().max(1));
+    let threads_per_state = (run.n_threads / parallel).max(1);
+
+    let total_samples = windows
+        .iter()
+        .map(|w| w.len())
+        .sum::<usize>()
+        .max(1);
+    let window_progress = Arc::new(Mutex::new(vec![0.0f32; windows.len()]));
+
+    let next_window = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<WindowTranscript>>>> = Mutex::new(
+        windows
+            .iter()
+            .map(|_| None)
+            .collect()
+    );
+
+    thread::scope(|scope| {
+        for _ in 0..parallel {
+            scope.spawn(|| {
+                // Each runner gets its own state, the model weights stay shared
+                let mut state = match ctx.create_state() {
+                    Ok(state) => state,
+                    Err(e) => {
+                        let i = next_window.fetch_add(1, Ordering::SeqCst);
+                        if let Some(slot) = results.lock().unwrap().get_mut(i) {
+                            *slot = Some(Err(anyhow!("Failed to create state: {}", e)));
+                        }
+                        return;
+                    }
+                };
+
+                loop {
+                    if run.cancelled.load(Ordering::SeqCst) {
+                        break;
+                    }
+
+                    let i = next_window.fetch_add(1, Ordering::SeqCst);
+                    let Some(window) = windows.get(i) else {
+                        break;
+                    };
+
+                    // whisper reports per window, weigh each one by its length
+                    let weight = (window.len() as f32) / (total_samples as f32);
+                    let window_progress = Arc::clone(&window_progress);
+                    let on_progress = Arc::clone(&run.on_progress);
+                    let report = move |percent: i32| {
+                        let mut progress = window_progress.lock().unwrap();
+                        progress[i] = ((percent as f32) / 100.0) * weight;
+                        on_progress(progress.iter().sum());
+                    };
+
+                    // Partial segments go out on the timeline of the whole recording
+                    let offset_ms = samples_to_ms(window.start);
+                    let on_segment = Arc::clone(&run.on_segment);
+                    let emit = move |data: SegmentCallbackData| {
+                        on_segment(TranscriptionSegment {
+                            id: data.segment.max(0) as u32,
+                            start_ms: offset_ms + centiseconds_to_ms(data.start_timestamp),
+                            end_ms: offset_ms + centiseconds_to_ms(data.end_timestamp),
+                            text: data.text,
+                            words: vec![],
+                            no_speech_prob: 0.0,
+                            avg_logprob: 0.0,
+                            speaker: None,
+                        });
+                    };
+
+                    let transcript = transcribe_window(
+                        ctx,
+                        &mut state,
+                        &pcm[window.clone()],
+                        threads_per_state,
+                        options,
+                        WindowHooks {
+                            on_progress: report,
+                            on_segment: emit,
+                            cancelled: Arc::clone(&run.cancelled),
+                        }
+                    );
+                    results.lock().unwrap()[i] = Some(transcript);
+                }
+            });
+        }
+    });
+
+    if run.cancelled.load(Ordering::SeqCst) {
+        return Err(anyhow!("Cancelled"));
+    }
+
+    // Stitch the windows back together in order
+    let mut segments: Vec<TranscriptionSegment> = Vec::new();
+    let mut turns = Vec::new();
+    let mut speech_per_language: HashMap<String, usize> = HashMap::new();
+
+    for (window, result) in windows.iter().zip(results.into_inner().unwrap()) {
+        let transcript = result.ok_or_else(|| anyhow!("Window was never transcribed"))??;
+
+        *speech_per_language.entry(transcript.language).or_default() += window.len();
+        stitch_window(&mut segments, window, transcript.segments);
+        turns.extend(transcript.turns);
+    }
+
+    // The language heard the longest wins
+    let language = speech_per_language
+        .into_iter()
+        .max_by_key(|(_, samples)| *samples)
+        .map(|(language, _)| language)
+        .or_else(|| options.language.clone())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    if options.diarize {
+        let spans: Vec<(u64, u64)> = segments
+            .iter()
+            .map(|s| (s.start_ms, s.end_ms))
+            .collect();
+        let max_speakers = options.max_speakers.map(|m| m as usize);
+        let speakers = diarization::diarize(pcm, &spans, &turns, max_speakers);
+
+        for (segment, speaker) in segments.iter_mut().zip(speakers) {
+            segment.speaker = Some(speaker);
+        }
     }
-    cmd.wait()?;
-    Ok(buf)
+
+    let text = segments
+        .iter()
+        .map(|s| s.text.trim())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(TranscriptionResponse {
+        text,
+        language,
+        segments,
+        model: None,
+        options: Some(options.clone()),
+        waveform: None,
+        thumbnail: None,
+    })
+}
+
+/// Receives the share of the work done, from 0.0 to 1.0
+pub type ProgressCallback = Arc<dyn Fn(f32) + Send + Sync>;
+
+/// Receives each segment as soon as whisper decodes it
+pub type SegmentCallback = Arc<dyn Fn(TranscriptionSegment) + Send + Sync>;
+
+/// How a transcription runs, as opposed to what it produces
+pub struct TranscribeRun {
+    pub n_threads: usize,
+    /// Windows transcribed at the same time
+    pub parallel: usize,
+    /// Receives the share of the audio transcribed so far
+    pub on_progress: ProgressCallback,
+    /// Receives segments before the windows are stitched together, ids are per window
+    /// and speakers are not known yet
+    pub on_segment: SegmentCallback,
+    /// Set from another thread to stop whisper as soon as possible
+    pub cancelled: Arc<AtomicBool>,
 }
 
-pub async fn whisper_transcribe(video_data: Vec<u8>) -> Result<TranscriptionResponse> {
-    // Convert video to audio
-    let audio_wav = extract_audio_from_video(&video_data)?;
-    let pcm = extract_chunks(&audio_wav);
+/// Segments of a single window, times relative to the start of the window
+struct WindowTranscript {
+    segments: Vec<TranscriptionSegment>,
+    /// Whether whisper saw a speaker change after each segment
+    turns: Vec<bool>,
+    language: String,
+}
+
+/// What whisper reports while a window runs, and the flag it checks to stop
+struct WindowHooks<P, S> {
+    /// Percent of the window done
+    on_progress: P,
+    on_segment: S,
+    cancelled: Arc<AtomicBool>,
+}
 
-    let path_to_model = "src/transcribe/assets/models/ggml-base.bin";
-    let ctx = WhisperContext::new_with_params(
-        path_to_model,
-        WhisperContextParameters::default()
-    ).map_err(|e| anyhow!("Model load failed: {}", e))?;
+fn transcribe_window(
+    ctx: &WhisperContext,
+    state: &mut WhisperState,
+    pcm: &[f32],
+    n_threads: usize,
+    options: &TranscriptionOptions,
+    hooks: WindowHooks<impl FnMut(i32) + 'static, impl FnMut(SegmentCallbackData) + 'static>
+) -> Result<WindowTranscript> {
+    let WindowHooks { on_progress, on_segment, cancelled } = hooks;
 
-    let mut state = ctx.create_state().map_err(|e| anyhow!("Failed to create state: {}", e))?;
+    let strategy = match options.beam_size {
+        Some(beam_size) =>
+            SamplingStrategy::BeamSearch {
+                beam_size: beam_size as i32,
+                patience: -1.0,
+            },
+        None => SamplingStrategy::Greedy { best_of: options.best_of as i32 },
+    };
 
-    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
-    params.set_n_threads(4);
-    params.set_translate(false);
-    params.set_language(None); // let whisper auto-detect
+    let mut params = FullParams::new(strategy);
+    params.set_n_threads(n_threads as i32);
+    params.set_translate(options.translate);
+    params.set_language(options.language.as_deref()); // None lets whisper auto-detect
+    params.set_temperature(options.temperature);
+    params.set_temperature_inc(options.temperature_increment);
+    // Speaker turns are only reported by tinydiarize models such as small.en-tdrz
+    params.set_tdrz_enable(options.diarize);
+    if let Some(prompt) = &options.initial_prompt {
+        params.set_initial_prompt(prompt);
+    }
+    params.set_token_timestamps(true);
+    params.set_progress_callback_safe(on_progress);
+    params.set_segment_callback_safe_lossy(on_segment);
+    params.set_abort_callback_safe(move || cancelled.load(Ordering::SeqCst));
 
     // Run transcription
-    state.full(params, &pcm).map_err(|e| anyhow!("Transcription failed: {}", e))?;
+    state.full(params, pcm).map_err(|e| anyhow!("Transcription failed: {}", e))?;
 
-    // Extract text + segments
-    let num = state.full_n_segments().map_err(|e| anyhow!("Fetching segments failed: {}", e))?;
-    let mut text = String::new();
     let mut segments = Vec::new();
+    let mut turns = Vec::new();
 
-    let lang_id = state.full_lang_id_from_state().unwrap_or(0);
-    let detected_lang = whisper_rs
-        ::get_lang_str(lang_id as i32)
+    let lang_id = state.full_lang_id_from_state();
+    let language = whisper_rs
+        ::get_lang_str(lang_id)
         .map(|s| s.to_string())
         .unwrap_or_else(|| "unknown".to_string());
 
-    for i in 0..num {
-        let seg_text = state.full_get_segment_text(i).unwrap_or_default();
-        let start_ms = state.full_get_segment_t0(i).unwrap_or(0);
-        let end_ms = state.full_get_segment_t1(i).unwrap_or(0);
+    // Every special token (timestamps, language, ...) sorts after end-of-text
+    let eot = ctx.token_eot();
 
-        // Convert ms → seconds
-        let start = (start_ms as f32) / 100.0;
-        let end = (end_ms as f32) / 100.0;
-
-        text.push_str(&seg_text);
-        text.push(' ');
+    for (i, segment) in state.as_iter().enumerate() {
+        let seg_text = segment.to_str_lossy().map(|t| t.into_owned()).unwrap_or_default();
+        let (words, avg_logprob) = segment_words(&segment, eot);
 
+        turns.push(segment.next_segment_speaker_turn());
         segments.push(TranscriptionSegment {
             id: i as u32,
-            start,
-            end,
+            start_ms: centiseconds_to_ms(segment.start_timestamp()),
+            end_ms: centiseconds_to_ms(segment.end_timestamp()),
             text: seg_text,
+            words,
+            no_speech_prob: segment.no_speech_probability(),
+            avg_logprob,
+            speaker: None,
         });
     }
 
-    Ok(TranscriptionResponse {
-        text: text.trim().to_owned(),
-        language: detected_lang,
-        segments: segments,
-    })
+    Ok(WindowTranscript { segments, turns, language })
+}
+
+/// Group the text tokens of a segment into words, a token starting with a space opens a new word
+///
+/// Also returns the mean log-probability of those tokens.
+fn segment_words(segment: &WhisperSegment, eot: WhisperTokenId) -> (Vec<TranscriptionWord>, f32) {
+    // Bytes rather than strings, a multi-byte character can be split over two tokens
+    let mut pending: Vec<(Vec<u8>, TranscriptionWord)> = Vec::new();
+    let mut logprob_sum = 0.0;
+    let mut text_tokens = 0;
+
+    for j in 0..segment.n_tokens() {
+        let Some(token) = segment.get_token(j) else {
+            continue;
+        };
+        let data = token.token_data();
+        if data.id >= eot {
+            continue;
+        }
+        let Ok(bytes) = token.to_bytes() else {
+            continue;
+        };
+
+        logprob_sum += data.plog;
+        text_tokens += 1;
+
+        match pending.last_mut() {
+            Some((word_bytes, word)) if !bytes.starts_with(b" ") => {
+                word_bytes.extend_from_slice(bytes);
+                word.end_ms = centiseconds_to_ms(data.t1).max(word.start_ms);
+                word.probability = word.probability.min(data.p);
+            }
+            _ => {
+                pending.push((
+                    bytes.to_vec(),
+                    TranscriptionWord {
+                        text: String::new(),
+                        start_ms: centiseconds_to_ms(data.t0),
+                        end_ms: centiseconds_to_ms(data.t1),
+                        probability: data.p,
+                    },
+                ));
+            }
+        }
+    }
+
+    let words = pending
+        .into_iter()
+        .filter_map(|(bytes, mut word)| {
+            word.text = String::from_utf8_lossy(&bytes).trim().to_string();
+            (!word.text.is_empty()).then_some(word)
+        })
+        .collect();
+
+    let avg_logprob = if text_tokens > 0 { logprob_sum / (text_tokens as f32) } else { 0.0 };
+
+    (words, avg_logprob)
+}
+
+/// Append the segments of a window, moved from its own timeline onto the whole recording's
+///
+/// whisper can place the end of a segment a little past the audio it was given, so times are
+/// kept within the window and never run into the next one.
+fn stitch_window(
+    segments: &mut Vec<TranscriptionSegment>,
+    window: &Range<usize>,
+    window_segments: Vec<TranscriptionSegment>
+) {
+    let offset_ms = samples_to_ms(window.start);
+    let window_end_ms = samples_to_ms(window.end);
+
+    for mut segment in window_segments {
+        segment.id = segments.len() as u32;
+        segment.start_ms = (segment.start_ms + offset_ms).min(window_end_ms);
+        segment.end_ms = (segment.end_ms + offset_ms).min(window_end_ms);
+        for word in &mut segment.words {
+            word.start_ms = (word.start_ms + offset_ms).min(window_end_ms);
+            word.end_ms = (word.end_ms + offset_ms).min(window_end_ms);
+        }
+        segments.push(segment);
+    }
+}
+
+fn samples_to_ms(samples: usize) -> u64 {
+    ((samples as u64) * 1000) / (WHISPER_SAMPLE_RATE as u64)
 }
 
-fn extract_chunks(data: &[u8]) -> Vec<f32> {
-    data.chunks_exact(2)
-        .map(|b| (i16::from_le_bytes([b[0], b[1]]) as f32) / (i16::MAX as f32))
-        .collect()
+/// whisper reports timestamps in 10ms units
+fn centiseconds_to_ms(t: i64) -> u64 {
+    (t.max(0) as u64) * 10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_ms: u64, end_ms: u64) -> TranscriptionSegment {
+        TranscriptionSegment {
+            id: 0,
+            start_ms,
+            end_ms,
+            text: String::new(),
+            words: vec![TranscriptionWord {
+                text: String::new(),
+                start_ms,
+                end_ms,
+                probability: 1.0,
+            }],
+            no_speech_prob: 0.0,
+            avg_logprob: 0.0,
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn stitched_segments_stay_in_order() {
+        let rate = WHISPER_SAMPLE_RATE as usize;
+        // 2s to 5s, then 5s to 9s, with whisper overshooting the end of the first window
+        let windows = [2 * rate..5 * rate, 5 * rate..9 * rate];
+        let mut segments = vec![];
+        stitch_window(&mut segments, &windows[0], vec![segment(0, 1_500), segment(1_500, 3_400)]);
+        stitch_window(&mut segments, &windows[1], vec![segment(0, 2_000), segment(2_000, 4_000)]);
+
+        let spans: Vec<(u32, u64, u64)> = segments
+            .iter()
+            .map(|s| (s.id, s.start_ms, s.end_ms))
+            .collect();
+        assert_eq!(
+            spans,
+            [(0, 2_000, 3_500), (1, 3_500, 5_000), (2, 5_000, 7_000), (3, 7_000, 9_000)]
+        );
+
+        for pair in segments.windows(2) {
+            assert!(pair[0].end_ms <= pair[1].start_ms);
+        }
+        for segment in &segments {
+            let word = &segment.words[0];
+            assert!(segment.start_ms <= word.start_ms && word.end_ms <= segment.end_ms);
+        }
+    }
 }
@@ -0,0 +1,294 @@
+use std::{ sync::Arc, time::Duration };
+
+use axum::{ body::{ to_bytes, Body }, http::{ Request, StatusCode }, Router };
+use serde_json::{ json, Value };
+use sha2::{ Digest, Sha256 };
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+use crate::{ modules::*, router, App };
+
+/// Result of the built-in mock script, as the canister parses it
+const MOCK_RESULT: &str = include_str!("../tests/fixtures/mock_result.json");
+
+const BOUNDARY: &str = "transcribe-test-boundary";
+
+/// Service running the mock engine on a journal of its own, removed with the returned directory
+fn app(signing_key: Option<&str>) -> (Router, TempDir) {
+    let data_dir = tempfile::tempdir().unwrap();
+    let config = Config::from_args(Args {
+        data_dir: Some(data_dir.path().display().to_string()),
+        engine: Some(EngineKind::Mock),
+        signing_key: signing_key.map(str::to_string),
+        ..Default::default()
+    }).expect("Test config");
+    let store = JobStore::open(&config.data_dir).unwrap();
+    let engine = MockTranscriber::new(MockScript::default(), &config.default_model);
+
+    (router(Arc::new(App::new(config, store)), Arc::new(engine)), data_dir)
+}
+
+async fn send(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = serde_json::from_slice(&body).unwrap_or_else(|_| {
+        Value::String(String::from_utf8_lossy(&body).into_owned())
+    });
+
+    (status, body)
+}
+
+fn post_json(uri: &str, body: Value) -> Request<Body> {
+    Request::post(uri)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::get(uri).body(Body::empty()).unwrap()
+}
+
+/// Signed request the canister's tests check with their signer too
+const SIGNED_REQUEST: &str = include_str!("../tests/fixtures/signed_request.json");
+
+/// Add the signature headers the canister would send, over `signed_body`
+fn sign(mut request: Request<Body>, key: &str, signed_body: &[u8]) -> Request<Body> {
+    let timestamp = unix_now();
+    let body_sha256 = hex::encode(Sha256::digest(signed_body));
+    let path = request.uri().path_and_query().unwrap().as_str().to_string();
+    let nonce = uuid::Uuid::new_v4().to_string();
+    let method = request.method().as_str().to_string();
+    let message = auth::signed_message(timestamp, &nonce, &method, &path, &body_sha256);
+
+    let headers = request.headers_mut();
+    headers.insert(auth::TIMESTAMP_HEADER, timestamp.into());
+    headers.insert(auth::NONCE_HEADER, nonce.parse().unwrap());
+    headers.insert(auth::CONTENT_SHA256_HEADER, body_sha256.parse().unwrap());
+    headers.insert(auth::SIGNATURE_HEADER, auth::sign_payload(key, &message).parse().unwrap());
+    request
+}
+
+fn chunk_upload(session_id: &str, chunk_index: usize, data: &[u8]) -> Request<Body> {
+    let mut body = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"session_id\"\r\n\r\n{session_id}\r\n\
+         --{b}\r\nContent-Disposition: form-data; name=\"chunk_index\"\r\n\r\n{chunk_index}\r\n\
+         --{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"chunk\"\r\n\
+         Content-Type: application/octet-stream\r\n\r\n",
+        b = BOUNDARY
+    ).into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+
+    Request::post("/upload_chunk")
+        .header("Content-Type", format!("multipart/form-data; boundary={}", BOUNDARY))
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn upload_to_result() {
+    let session_id = format!("session-{}", uuid::Uuid::new_v4());
+    let chunks: [&[u8]; 2] = [b"first half of the media, ", b"second half"];
+    let total_size = chunks.iter().map(|c| c.len()).sum::<usize>();
+    let (app, _data_dir) = app(None);
+
+    let (status, session) = send(
+        &app,
+        post_json(
+            "/sessions",
+            json!({ "session_id": session_id, "total_chunks": 2, "total_size": total_size })
+        )
+    ).await;
+    assert_eq!(status, StatusCode::OK, "{}", session);
+    assert_eq!(session["missing_chunks"], json!([0, 1]));
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let (status, body) = send(&app, chunk_upload(&session_id, i, chunk)).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+    }
+
+    let (status, session) = send(&app, get(&format!("/sessions/{}", session_id))).await;
+    assert_eq!(status, StatusCode::OK, "{}", session);
+    assert_eq!(session["missing_chunks"], json!([]));
+    assert_eq!(session["received_size"], json!(total_size));
+
+    let (status, started) = send(
+        &app,
+        post_json("/finalize_upload", json!({ "session_id": session_id }))
+    ).await;
+    assert_eq!(status, StatusCode::OK, "{}", started);
+    let job_id = started["message"]
+        .as_str()
+        .and_then(|m| m.strip_prefix("Job started with ID: "))
+        .expect("job id in the finalize reply")
+        .to_string();
+
+    // A retried finalize names the same job rather than starting another
+    let (status, again) = send(
+        &app,
+        post_json("/finalize_upload", json!({ "session_id": session_id }))
+    ).await;
+    assert_eq!(status, StatusCode::OK, "{}", again);
+    assert_eq!(again, started);
+
+    let mut polls = 0;
+    loop {
+        let (status, body) = send(&app, get(&format!("/status/{}", job_id))).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        match body["status"].as_str() {
+            Some("Completed") => {
+                // The transcript only comes from /result
+                assert!(body.get("data").is_none(), "{}", body);
+                break;
+            }
+            Some("Pending") | Some("InProgress") => {}
+            _ => panic!("Job did not complete: {}", body),
+        }
+
+        polls += 1;
+        assert!(polls < 100, "Job {} still running after 10s", job_id);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let (status, result) = send(&app, get(&format!("/result/{}?offset=0&limit=100", job_id))).await;
+    assert_eq!(status, StatusCode::OK, "{}", result);
+    let expected: Value = serde_json::from_str(MOCK_RESULT).unwrap();
+    assert_eq!(result, expected);
+
+    // A later page has the remaining segments and no waveform or thumbnail
+    let (status, page) = send(&app, get(&format!("/result/{}?offset=2&limit=100", job_id))).await;
+    assert_eq!(status, StatusCode::OK, "{}", page);
+    assert_eq!(page["offset"], json!(2));
+    assert_eq!(page["total_segments"], json!(3));
+    assert_eq!(page["segments"], json!([expected["segments"][2]]));
+    assert_eq!(page["waveform"], Value::Null);
+
+    // Still the same job once it is done and its session is gone
+    let (status, again) = send(
+        &app,
+        post_json("/finalize_upload", json!({ "session_id": session_id }))
+    ).await;
+    assert_eq!(status, StatusCode::OK, "{}", again);
+    assert_eq!(again, started);
+}
+
+#[tokio::test]
+async fn event_stream_token() {
+    let key = "test-key";
+    let (app, _data_dir) = app(Some(key));
+    let events = |job_id: &str, token: Option<String>| {
+        let query = token.map(|t| format!("?token={}", t)).unwrap_or_default();
+        app.clone().oneshot(get(&format!("/events/{}{}", job_id, query)))
+    };
+    let token = |job_id: &str, expires: u64| {
+        let message = auth::event_token_message(job_id, expires);
+        format!("{}.{}", expires, auth::sign_payload(key, &message))
+    };
+    let expires = unix_now() + 60;
+
+    let response = events("job-1", None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // A token only opens the stream of the job it was minted for
+    let response = events("job-1", Some(token("job-2", expires))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = events("job-1", Some(token("job-1", unix_now() - 1))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Past the check, the job itself does not exist
+    let response = events("job-1", Some(token("job-1", expires))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // The rest of the API still wants signed headers, a token is no use there
+    let response = app
+        .oneshot(get(&format!("/status/job-1?token={}", token("job-1", expires))))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn signed_upload_rejections() {
+    let key = "test-key";
+    let (app, _data_dir) = app(Some(key));
+    let send = |request: Request<Body>| {
+        let app = app.clone();
+        async move {
+            let response = app.oneshot(request).await.unwrap();
+            let status = response.status();
+            (status, to_bytes(response.into_body(), usize::MAX).await.unwrap())
+        }
+    };
+    // Signed over the body `request` builds, sent with the body `sent` builds
+    let signed = |request: fn() -> Request<Body>, sent: fn() -> Request<Body>| async move {
+        let body = to_bytes(request().into_body(), usize::MAX).await.unwrap();
+        sign(sent(), key, &body)
+    };
+
+    fn declare() -> Request<Body> {
+        let session = json!({ "session_id": "signed-1", "total_chunks": 1, "total_size": 4 });
+        post_json("/sessions", session)
+    }
+    fn chunk() -> Request<Body> {
+        chunk_upload("signed-1", 0, b"data")
+    }
+    fn forged_chunk() -> Request<Body> {
+        chunk_upload("signed-1", 0, b"evil")
+    }
+    fn unknown_chunk() -> Request<Body> {
+        chunk_upload("signed-unknown", 0, b"data")
+    }
+
+    let declared = signed(declare, declare).await;
+    let declared_headers = declared.headers().clone();
+    let (status, first_reply) = send(declared).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Unsigned, nothing is read
+    let (status, _) = send(chunk()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // A body other than the one signed, and a chunk for a session that does not exist, are
+    // refused alike
+    let forged = send(signed(chunk, forged_chunk).await).await;
+    let unknown = send(signed(unknown_chunk, unknown_chunk).await).await;
+    assert_eq!(forged.0, StatusCode::UNAUTHORIZED);
+    assert_eq!(forged, unknown);
+
+    let (status, session) = send(sign(get("/sessions/signed-1"), key, b"")).await;
+    assert_eq!(status, StatusCode::OK);
+    let session: Value = serde_json::from_slice(&session).unwrap();
+    assert_eq!(session["missing_chunks"], json!([0]));
+
+    let (status, _) = send(signed(chunk, chunk).await).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Sent again, the declaration is not run again but answered as the first time, when the
+    // chunk was still missing
+    let mut replayed = declare();
+    *replayed.headers_mut() = declared_headers;
+    let (status, reply) = send(replayed).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(reply, first_reply);
+}
+
+#[test]
+fn verifies_like_the_canister_signs() {
+    let vector: Value = serde_json::from_str(SIGNED_REQUEST).unwrap();
+    let field = |name: &str| vector[name].as_str().unwrap().to_string();
+
+    assert_eq!(hex::encode(Sha256::digest(field("body").as_bytes())), field("content_sha256"));
+
+    let message = auth::signed_message(
+        vector["timestamp"].as_u64().unwrap(),
+        &field("nonce"),
+        &field("method"),
+        &field("path"),
+        &field("content_sha256")
+    );
+    let signature = hex::decode(field("signature")).unwrap();
+    assert!(auth::verify_payload(&field("key"), &message, &signature));
+}